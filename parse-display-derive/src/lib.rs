@@ -18,20 +18,22 @@ use once_cell::sync::Lazy;
 use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, quote};
 use regex::{Captures, Regex};
-use regex_syntax::hir::Hir;
+use regex_syntax::hir::{Capture, Hir, HirKind, Look, Repetition};
 use std::{
+    cmp::Reverse,
     collections::{BTreeMap, HashMap},
     fmt::{Display, Formatter},
     ops::{Deref, DerefMut},
 };
-use structmeta::{Flag, StructMeta, ToTokens};
+use structmeta::{Flag, NameArgs, NameValue, StructMeta, ToTokens};
 use syn::{
     ext::IdentExt,
     parse::{discouraged::Speculative, Parse, ParseStream},
     parse_macro_input, parse_quote, parse_str,
     spanned::Spanned,
     Attribute, Data, DataEnum, DataStruct, DeriveInput, Expr, Field, Fields, FieldsNamed,
-    FieldsUnnamed, Ident, LitStr, Member, Path, Result, Token, Type, Variant, WherePredicate,
+    FieldsUnnamed, Ident, LitBool, LitInt, LitStr, Member, Path, Result, Token, Type, Variant,
+    WherePredicate,
 };
 
 #[proc_macro_derive(Display, attributes(display))]
@@ -40,16 +42,38 @@ pub fn derive_display(input: proc_macro::TokenStream) -> proc_macro::TokenStream
     into_macro_output(match &input.data {
         Data::Struct(data) => derive_display_for_struct(&input, data),
         Data::Enum(data) => derive_display_for_enum(&input, data),
-        _ => panic!("`#[derive(Display)]` supports only enum or struct."),
+        Data::Union(data) => Err(syn::Error::new(
+            data.union_token.span,
+            "`#[derive(Display)]` supports only enum or struct.",
+        )),
     })
 }
 
 fn derive_display_for_struct(input: &DeriveInput, data: &DataStruct) -> Result<TokenStream> {
     let hattrs = HelperAttributes::from(&input.attrs)?;
-    let ctx = DisplayContext::Struct { data };
+    if let Some(span) = hattrs.discriminant {
+        bail!(
+            span,
+            "`#[display(discriminant)]` cannot be specified for struct."
+        );
+    }
+    let ctx = DisplayContext::Struct {
+        data,
+        style: DisplayStyle::from_helper_attributes_struct(&hattrs),
+        crate_path: &hattrs.crate_path,
+    };
     let generics = GenericParamSet::new(&input.generics);
 
     let mut format = hattrs.format;
+    if let Some(kv) = &hattrs.kv {
+        if format.is_some() {
+            bail!(
+                kv.span,
+                "`#[display(kv)]` cannot be specified with `#[display(\"format\")]`."
+            );
+        }
+        format = Some(DisplayFormat::from_kv(&data.fields, kv)?);
+    }
     if format.is_none() {
         format = DisplayFormat::from_newtype_struct(data);
     }
@@ -74,12 +98,14 @@ fn derive_display_for_struct(input: &DeriveInput, data: &DataStruct) -> Result<T
             }
         },
         hattrs.dump_display,
+        "",
     )
 }
 fn derive_display_for_enum(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream> {
     fn make_arm(
         hattrs_enum: &HelperAttributes,
         variant: &Variant,
+        discriminant: Option<&Discriminant>,
         bounds: &mut Bounds,
         generics: &GenericParamSet,
     ) -> Result<TokenStream> {
@@ -101,13 +127,14 @@ fn derive_display_for_enum(input: &DeriveInput, data: &DataEnum) -> Result<Token
             Fields::Unit => quote! {},
         };
         let hattrs_variant = HelperAttributes::from(&variant.attrs)?;
+        hattrs_variant.check_kv_not_specified()?;
         let style = DisplayStyle::from_helper_attributes(hattrs_enum, &hattrs_variant);
         let mut format = hattrs_variant.format;
         if format.is_none() {
             format = hattrs_enum.format.clone();
         }
         if format.is_none() {
-            format = DisplayFormat::from_unit_variant(variant)?;
+            format = DisplayFormat::from_unit_variant(variant, discriminant.is_some())?;
         }
         let format = match format {
             Some(x) => x,
@@ -118,7 +145,12 @@ fn derive_display_for_enum(input: &DeriveInput, data: &DataEnum) -> Result<Token
         };
         let variant_ident = &variant.ident;
         let args = format.format_args(
-            DisplayContext::Variant { variant, style },
+            DisplayContext::Variant {
+                variant,
+                style,
+                discriminant,
+                crate_path: &hattrs_enum.crate_path,
+            },
             &mut bounds.child(hattrs_variant.bound_display),
             generics,
         )?;
@@ -129,11 +161,20 @@ fn derive_display_for_enum(input: &DeriveInput, data: &DataEnum) -> Result<Token
         })
     }
     let hattrs = HelperAttributes::from(&input.attrs)?;
+    hattrs.check_kv_not_specified()?;
+    let discriminants = Discriminant::from_enum(input, data, &hattrs)?;
     let mut bounds = Bounds::from_data(hattrs.bound_display.clone());
     let generics = GenericParamSet::new(&input.generics);
     let mut arms = Vec::new();
-    for variant in &data.variants {
-        arms.push(make_arm(&hattrs, variant, &mut bounds, &generics)?);
+    for (index, variant) in data.variants.iter().enumerate() {
+        let discriminant = discriminants.as_ref().map(|ds| &ds[index]);
+        arms.push(make_arm(
+            &hattrs,
+            variant,
+            discriminant,
+            &mut bounds,
+            &generics,
+        )?);
     }
     let trait_path = parse_quote!(::core::fmt::Display);
     let contents = quote! {
@@ -144,7 +185,14 @@ fn derive_display_for_enum(input: &DeriveInput, data: &DataEnum) -> Result<Token
         }
     };
     let wheres = bounds.build_wheres(&trait_path);
-    impl_trait_result(input, &trait_path, &wheres, contents, hattrs.dump_display)
+    impl_trait_result(
+        input,
+        &trait_path,
+        &wheres,
+        contents,
+        hattrs.dump_display,
+        "",
+    )
 }
 
 #[proc_macro_derive(FromStr, attributes(display, from_str))]
@@ -153,16 +201,37 @@ pub fn derive_from_str(input: proc_macro::TokenStream) -> proc_macro::TokenStrea
     into_macro_output(match &input.data {
         Data::Struct(data) => derive_from_str_for_struct(&input, data),
         Data::Enum(data) => derive_from_str_for_enum(&input, data),
-        _ => panic!("`#[derive(FromStr)]` supports only enum or struct."),
+        Data::Union(data) => Err(syn::Error::new(
+            data.union_token.span,
+            "`#[derive(FromStr)]` supports only enum or struct.",
+        )),
     })
 }
 fn derive_from_str_for_struct(input: &DeriveInput, data: &DataStruct) -> Result<TokenStream> {
     let hattrs = HelperAttributes::from(&input.attrs)?;
-    let p = ParserBuilder::from_struct(&hattrs, data)?;
+    if let Some(span) = hattrs.discriminant {
+        bail!(
+            span,
+            "`#[display(discriminant)]` cannot be specified for struct."
+        );
+    }
+    if let Some((_, span)) = hattrs.priority {
+        bail!(
+            span,
+            "`#[from_str(priority = ...)]` cannot be specified for struct."
+        );
+    }
+    if let Some((_, span)) = hattrs.match_policy {
+        bail!(
+            span,
+            "`#[from_str(match = \"...\")]` cannot be specified for struct."
+        );
+    }
+    let generics = GenericParamSet::new(&input.generics);
+    let p = ParserBuilder::from_struct(&hattrs, data, &generics)?;
     let crate_path = &hattrs.crate_path;
     let trait_path = parse_quote!(::core::str::FromStr);
     let body = p.build_from_str_body(crate_path, parse_quote!(Self))?;
-    let generics = GenericParamSet::new(&input.generics);
     let mut bounds = Bounds::from_data(hattrs.bound_from_str_resolved());
     p.build_bounds(&generics, &mut bounds);
     let wheres = bounds.build_wheres(&trait_path);
@@ -170,121 +239,695 @@ fn derive_from_str_for_struct(input: &DeriveInput, data: &DataStruct) -> Result<
         input,
         &trait_path,
         &wheres,
-        quote! {
+        quote_hygienic! {
             type Err = #crate_path::ParseError;
             fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
                 #body
             }
         },
         hattrs.dump_from_str,
+        &p.build_dump_notes(),
     )
 }
 fn derive_from_str_for_enum(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream> {
     let hattrs_enum = HelperAttributes::from(&input.attrs)?;
+    hattrs_enum.check_kv_not_specified()?;
     if let Some(span) = hattrs_enum.default_self {
         bail!(span, "`#[from_str(default)]` cannot be specified for enum.");
     }
+    if let Some(sep) = &hattrs_enum.any_order {
+        bail!(
+            sep.span(),
+            "`#[from_str(any_order)]` cannot be specified for enum."
+        );
+    }
+    if let Some((_, span)) = hattrs_enum.priority {
+        bail!(
+            span,
+            "`#[from_str(priority = ...)]` cannot be specified for enum."
+        );
+    }
+    let discriminants = Discriminant::from_enum(input, data, &hattrs_enum)?;
     let crate_path = &hattrs_enum.crate_path;
     let trait_path = parse_quote!(::core::str::FromStr);
     let mut bounds = Bounds::from_data(hattrs_enum.bound_from_str_resolved());
     let generics = GenericParamSet::new(&input.generics);
-    let mut bodys = Vec::new();
-    let mut arms = Vec::new();
-    for variant in data.variants.iter() {
+    let match_policy = hattrs_enum
+        .match_policy
+        .map_or(MatchPolicy::First, |(policy, _)| policy);
+    let mut literals = HashMap::new();
+    let mut spellings = Some(Vec::new());
+    let mut variants = Vec::new();
+    let mut dump_notes = String::new();
+    for (index, variant) in data.variants.iter().enumerate() {
         let hattrs_variant = HelperAttributes::from(&variant.attrs)?;
+        hattrs_variant.check_kv_not_specified()?;
+        if let Some((_, span)) = hattrs_variant.match_policy {
+            bail!(
+                span,
+                "`#[from_str(match = \"...\")]` cannot be specified for variant."
+            );
+        }
         if hattrs_variant.ignore.value() {
             continue;
         }
         let variant_ident = &variant.ident;
         let constructor = parse_quote!(Self::#variant_ident);
-        let p = ParserBuilder::from_variant(&hattrs_variant, &hattrs_enum, variant)?;
+        let discriminant = discriminants.as_ref().map(|ds| &ds[index]);
+        let p = ParserBuilder::from_variant(
+            &hattrs_variant,
+            &hattrs_enum,
+            variant,
+            discriminant,
+            &generics,
+        )?;
         let mut bounds = bounds.child(hattrs_variant.bound_from_str_resolved());
         p.build_bounds(&generics, &mut bounds);
-        match p.build_parse_variant_code(crate_path, constructor)? {
-            ParseVariantCode::MatchArm(arm) => arms.push(arm),
-            ParseVariantCode::Statement(body) => bodys.push(body),
+        if let ParseFormat::String(s) = &p.parse_format {
+            if let Some(other) = literals.get(s) {
+                bail!(
+                    p.span,
+                    "variant `{variant_ident}` cannot be parsed, because its format `{s}` is the same as that of variant `{other}`."
+                );
+            }
+            literals.insert(s.clone(), variant_ident);
+            if let Some(spellings) = &mut spellings {
+                spellings.push(s.clone());
+            }
+        } else {
+            spellings = None;
         }
+        let priority = hattrs_variant.priority.map_or(0, |(priority, _)| priority);
+        let literal_len = match match_policy {
+            MatchPolicy::First => 0,
+            MatchPolicy::Longest => p.parse_format.literal_len(),
+        };
+        if hattrs_enum.dump_from_str.is_some() {
+            dump_notes.push_str(&format!("variant `{variant_ident}`:\n"));
+            dump_notes.push_str(&p.build_dump_notes());
+        }
+        let infallible = p.is_infallible();
+        let code = p.build_parse_variant_code(crate_path, constructor)?;
+        let is_statement = matches!(code, ParseVariantCode::Statement(_));
+        let format = if p.exprs.is_empty() && p.type_regexes.is_empty() {
+            Some(p.parse_format)
+        } else {
+            None
+        };
+        variants.push((
+            (Reverse(priority), Reverse(literal_len), is_statement),
+            ShadowingCheck {
+                variant_ident,
+                format,
+                infallible,
+            },
+            code,
+        ));
     }
-    let match_body = if arms.is_empty() {
-        quote! {}
-    } else {
-        quote! {
-            match s {
-                #(#arms,)*
-                _ => { }
+    variants.sort_by_key(|(key, _, _)| *key);
+    let checks: Vec<_> = variants.iter().map(|(_, check, _)| check).collect();
+    let warnings = build_shadowed_variant_warnings(&checks);
+    let mut bodys = Vec::new();
+    let mut arms = Vec::new();
+    for (_, _, code) in variants {
+        match code {
+            ParseVariantCode::MatchArm(arm) => arms.push(arm),
+            ParseVariantCode::Statement(body) => {
+                bodys.extend(build_match_arms(&mut arms));
+                bodys.push(quote_hygienic! { { #body } });
             }
         }
-    };
+    }
+    bodys.extend(build_match_arms(&mut arms));
     let wheres = bounds.build_wheres(&trait_path);
-    impl_trait_result(
+    let mut ts = impl_trait_result(
         input,
         &trait_path,
         &wheres,
-        quote! {
+        quote_hygienic! {
             type Err = #crate_path::ParseError;
             fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
-                #match_body
-                #({ #bodys })*
+                #(#warnings)*
+                #(#bodys)*
                 ::core::result::Result::Err(#crate_path::ParseError::new())
             }
         },
         hattrs_enum.dump_from_str,
+        &dump_notes,
+    )?;
+    if let Some(mut spellings) = spellings.filter(|spellings| !spellings.is_empty()) {
+        // Longer spellings come first, so that a field of this type matches as long as possible.
+        spellings.sort_by_key(|s| Reverse(s.len()));
+        let regex = join(spellings.iter().map(|s| regex_syntax::escape(s)), "|");
+        ts.extend(impl_trait(
+            input,
+            &parse_quote!(#crate_path::FromStrRegex),
+            &[],
+            quote! { const REGEX: &'static str = #regex; },
+        ));
+    }
+    Ok(ts)
+}
+
+fn build_match_arms(arms: &mut Vec<TokenStream>) -> Option<TokenStream> {
+    if arms.is_empty() {
+        return None;
+    }
+    let arms = std::mem::take(arms);
+    Some(quote_hygienic! {
+        match s {
+            #(#arms,)*
+            _ => { }
+        }
+    })
+}
+
+struct ShadowingCheck<'a> {
+    variant_ident: &'a Ident,
+    format: Option<ParseFormat>,
+    infallible: bool,
+}
+
+/// Warns about variants that can never be parsed,
+/// because every string they match is taken by an earlier variant that never fails.
+///
+/// `variants` must be in the order in which they are tried.
+fn build_shadowed_variant_warnings(variants: &[&ShadowingCheck]) -> Vec<TokenStream> {
+    let mut warnings = Vec::new();
+    for (index, v) in variants.iter().enumerate() {
+        let format = match &v.format {
+            Some(format) => format,
+            None => continue,
+        };
+        let earlier: Vec<_> = variants[..index]
+            .iter()
+            .filter(|e| e.infallible)
+            .filter_map(|e| Some((e.variant_ident, e.format.as_ref()?)))
+            .collect();
+        let mut shadowing = Vec::new();
+        let (strings, hir) = match format {
+            ParseFormat::String(s) => (Some(vec![s.clone()]), None),
+            ParseFormat::Hirs(hirs) => {
+                let hir = to_hir(&to_regex_string(hirs));
+                (to_exact_strings(&hir), Some(hir))
+            }
+        };
+        if let Some(ss) = strings {
+            for s in &ss {
+                let ident = earlier
+                    .iter()
+                    .find(|(_, format)| match format {
+                        ParseFormat::String(l) => l == s,
+                        ParseFormat::Hirs(hirs) => {
+                            Regex::new(&to_regex_string(hirs)).unwrap().is_match(s)
+                        }
+                    })
+                    .map(|(ident, _)| *ident);
+                match ident {
+                    Some(ident) => {
+                        if !shadowing.contains(&ident) {
+                            shadowing.push(ident);
+                        }
+                    }
+                    None => {
+                        shadowing.clear();
+                        break;
+                    }
+                }
+            }
+        } else if let Some(hir) = hir {
+            let hir = normalize(&hir);
+            let any = normalize(&to_hir(r"^(?s:.*)$"));
+            if let Some((ident, _)) = earlier.iter().find(|(_, format)| match format {
+                ParseFormat::String(_) => false,
+                ParseFormat::Hirs(hirs) => {
+                    let earlier_hir = normalize(&to_hir(&to_regex_string(hirs)));
+                    earlier_hir == hir || earlier_hir == any
+                }
+            }) {
+                shadowing.push(ident);
+            }
+        }
+        if !shadowing.is_empty() {
+            let variant_ident = v.variant_ident;
+            let shadowing = shadowing
+                .iter()
+                .map(|ident| format!("`{ident}`"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            warnings.push(build_warning(
+                variant_ident.span(),
+                &format!(
+                    "variant `{variant_ident}` is never parsed, because the input it matches is parsed as {shadowing}."
+                ),
+            ));
+        }
+    }
+    warnings
+}
+
+#[proc_macro_derive(FieldsByName, attributes(display, from_str))]
+pub fn derive_fields_by_name(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    into_macro_output(match &input.data {
+        Data::Struct(data) => derive_fields_by_name_for_struct(&input, data),
+        Data::Enum(data) => Err(syn::Error::new(
+            data.enum_token.span,
+            "`#[derive(FieldsByName)]` supports only struct.",
+        )),
+        Data::Union(data) => Err(syn::Error::new(
+            data.union_token.span,
+            "`#[derive(FieldsByName)]` supports only struct.",
+        )),
+    })
+}
+fn derive_fields_by_name_for_struct(input: &DeriveInput, data: &DataStruct) -> Result<TokenStream> {
+    let hattrs = HelperAttributes::from(&input.attrs)?;
+    let crate_path = &hattrs.crate_path;
+    let style = DisplayStyle::from_helper_attributes_struct(&hattrs);
+    let generics = GenericParamSet::new(&input.generics);
+    let mut bounds_display = Bounds::from_data(hattrs.bound_display.clone());
+    let mut bounds_from_str = Bounds::from_data(hattrs.bound_from_str_resolved());
+    let mut names = Vec::new();
+    let mut fmt_arms = Vec::new();
+    let mut vars = Vec::new();
+    let mut setters = Vec::new();
+    for (index, field) in data.fields.iter().enumerate() {
+        let key = match &field.ident {
+            Some(ident) => FieldKey::from_ident(ident),
+            None => FieldKey::Unnamed(index),
+        };
+        let name = key.to_string();
+        let format = DisplayFormat::parse(&format!("{{{key}}}"), field.span())?;
+        let args = format.format_args(
+            DisplayContext::Struct {
+                data,
+                style,
+                crate_path,
+            },
+            &mut bounds_display,
+            &generics,
+        )?;
+        fmt_arms.push(quote! {
+            #name => ::core::option::Option::Some(::core::write!(f, #args)),
+        });
+
+        let hattrs_field = HelperAttributes::from(&field.attrs)?;
+        let mut bounds = bounds_from_str.child(hattrs_field.bound_from_str_resolved());
+        if bounds.can_extend && generics.contains_in_type(&field.ty) {
+            bounds.ty.push(field.ty.clone());
+        }
+        let mut p = ParserBuilder::new(&data.fields, &generics)?;
+        p.apply_attrs(&hattrs)?;
+        let context = DisplayContext::Struct {
+            data,
+            style,
+            crate_path,
+        };
+        p.push_field(&context, &key, field.span())?;
+        let parse_expr = p.build_field_parse_expr(crate_path, &key, field.span())?;
+        let default_expr = if let Some(expr) = &hattrs_field.default_expr {
+            Some(quote_hygienic! { #expr })
+        } else if hattrs_field.default_self.is_some() {
+            Some(quote_hygienic! { ::core::default::Default::default() })
+        } else {
+            None
+        };
+        if hattrs.default_self.is_some() {
+            let default_setter =
+                default_expr.map(|expr| quote_hygienic! { else { value.#key = #expr; } });
+            setters.push(quote_hygienic! {
+                if let ::core::option::Option::Some(s) = fields(#name) {
+                    value.#key = #parse_expr;
+                } #default_setter
+            });
+        } else {
+            let var = key.new_arg_var();
+            let missing = default_expr.unwrap_or_else(|| {
+                let msg = format!("field `{name}` is missing.");
+                quote_hygienic! {
+                    return ::core::result::Result::Err(#crate_path::ParseError::with_message(#msg))
+                }
+            });
+            vars.push(quote_hygienic! {
+                let #var = match fields(#name) {
+                    ::core::option::Option::Some(s) => #parse_expr,
+                    ::core::option::Option::None => #missing,
+                };
+            });
+            setters.push(quote_hygienic! { #var });
+        }
+        names.push(name);
+    }
+    let construct = if hattrs.default_self.is_some() {
+        let default_expr = if let Some(expr) = &hattrs.default_expr {
+            quote_hygienic! { #expr }
+        } else {
+            quote_hygienic! { <Self as ::core::default::Default>::default() }
+        };
+        quote_hygienic! {
+            let mut value = #default_expr;
+            #(#setters)*
+            ::core::result::Result::Ok(value)
+        }
+    } else {
+        let keys = data
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| match &field.ident {
+                Some(ident) => FieldKey::from_ident(ident),
+                None => FieldKey::Unnamed(index),
+            });
+        let ps = match &data.fields {
+            Fields::Named(..) => quote_hygienic! { { #(#keys : #setters,)* } },
+            Fields::Unnamed(..) => quote_hygienic! { ( #(#setters,)* ) },
+            Fields::Unit => quote_hygienic! {},
+        };
+        quote_hygienic! {
+            #(#vars)*
+            ::core::result::Result::Ok(Self #ps)
+        }
+    };
+    let from_fields = quote_hygienic! {
+        fn from_fields<'a>(
+            fields: &dyn Fn(&str) -> ::core::option::Option<&'a str>,
+        ) -> ::core::result::Result<Self, #crate_path::ParseError> {
+            #construct
+        }
+    };
+    let trait_path = parse_quote!(#crate_path::FieldsByName);
+    let mut wheres = bounds_display.build_wheres(&parse_quote!(::core::fmt::Display));
+    wheres.extend(bounds_from_str.build_wheres(&parse_quote!(::core::str::FromStr)));
+    impl_trait_result(
+        input,
+        &trait_path,
+        &wheres,
+        quote! {
+            const FIELD_NAMES: &'static [&'static str] = &[#(#names),*];
+            fn fmt_field(
+                &self,
+                name: &str,
+                f: &mut ::core::fmt::Formatter,
+            ) -> ::core::option::Option<::core::fmt::Result> {
+                match name {
+                    #(#fmt_arms)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+            #from_fields
+        },
+        hattrs.dump_display.or(hattrs.dump_from_str),
+        "",
     )
 }
 
+#[proc_macro]
+pub fn parse(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as ParseMacroInput);
+    into_macro_output(build_parse_macro(&input))
+}
+
+struct ParseMacroInput {
+    format: LitStr,
+    input: Expr,
+}
+impl Parse for ParseMacroInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let format = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let value = input.parse()?;
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+        Ok(Self {
+            format,
+            input: value,
+        })
+    }
+}
+
+fn build_parse_macro(input: &ParseMacroInput) -> Result<TokenStream> {
+    let span = input.format.span();
+    let mut format = DisplayFormat::parse_lit_str(&input.format)?;
+    let mut next_index = 0;
+    let mut max_index = None;
+    let mut names = Vec::new();
+    for part in &mut format.parts {
+        match part {
+            DisplayFormatPart::Var { arg, .. } => {
+                if arg.is_empty() {
+                    *arg = next_index.to_string();
+                    next_index += 1;
+                }
+                if let Ok(index) = arg.parse::<usize>() {
+                    max_index = max_index.max(Some(index));
+                } else if parse_str::<Ident>(arg).is_ok() {
+                    if !names.contains(arg) {
+                        names.push(arg.clone());
+                    }
+                } else if !arg.contains("::") {
+                    bail!(span, "`{{{arg}}}` cannot be used in `parse!`.");
+                }
+            }
+            DisplayFormatPart::Cond { .. } | DisplayFormatPart::FieldName { .. } => {
+                bail!(
+                    span,
+                    "`{{?...}}` and `{{#...}}` cannot be used in `parse!`."
+                );
+            }
+            _ => {}
+        }
+    }
+    if max_index.is_some() && !names.is_empty() {
+        bail!(
+            span,
+            "positional and named arguments cannot be mixed in `parse!`."
+        );
+    }
+    let data: DeriveInput = if names.is_empty() {
+        let types = (0..max_index.map_or(0, |index| index + 1)).map(|_| quote!(_));
+        parse_quote!(struct ParseOutput(#(#types),*);)
+    } else {
+        let names = names.iter().map(|name| format_ident!("{}", name));
+        parse_quote!(struct ParseOutput { #(#names: _),* })
+    };
+    let generics = GenericParamSet::new(&data.generics);
+    let data = match &data.data {
+        Data::Struct(data) => data,
+        _ => unreachable!(),
+    };
+    let keys: Vec<_> = data
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| match &field.ident {
+            Some(ident) => FieldKey::from_ident(ident),
+            None => FieldKey::Unnamed(index),
+        })
+        .collect();
+    let vars = keys.iter().map(|key| key.new_arg_var());
+    let mut hattrs = HelperAttributes::from(&[])?;
+    hattrs.format = Some(format);
+    hattrs.new_expr = Some(parse_quote!((#(#vars,)*)));
+    let p = ParserBuilder::from_struct(&hattrs, data, &generics)?;
+    let crate_path = &hattrs.crate_path;
+    let body = p.build_from_str_body(crate_path, parse_quote!(Self))?;
+    let value = &input.input;
+    let types = keys.iter().map(|_| quote!(_));
+    let parse = quote_hygienic! {
+        (|s: &str| -> ::core::result::Result<(#(#types,)*), #crate_path::ParseError> {
+            #body
+        })(::core::convert::AsRef::<str>::as_ref(&#value))
+    };
+    if names.is_empty() {
+        return Ok(parse);
+    }
+    let values: Vec<_> = (0..names.len())
+        .map(|index| Ident::new(&format!("value_{index}"), Span::mixed_site()))
+        .collect();
+    let names = names
+        .iter()
+        .map(|name| format_ident!("{}", name, span = span));
+    Ok(quote_hygienic! {
+        #parse.map(|(#(#values,)*)| { #(#names = #values;)* })
+    })
+}
+
 struct ParserBuilder<'a> {
     capture_next: usize,
     parse_format: ParseFormat,
     fields: BTreeMap<FieldKey, FieldEntry<'a>>,
     source: &'a Fields,
+    generics: &'a GenericParamSet,
     use_default: bool,
     span: Span,
     new_expr: Option<Expr>,
+    validate: Option<Path>,
+    default_expr: Option<Expr>,
+    discriminant_capture: Option<(usize, Type, Option<ParseMethod>)>,
+    any_order: Option<(String, Vec<AnyOrderSegment>)>,
+    exprs: Vec<(String, Expr, String)>,
+    type_regexes: Vec<(String, Type, bool, String)>,
+    infer_regex: bool,
+    whitespace: Whitespace,
+    greedy: bool,
+    non_empty: bool,
 }
 struct FieldEntry<'a> {
     hattrs: HelperAttributes,
     deep_captures: BTreeMap<Vec<FieldKey>, usize>,
     source: &'a Field,
     capture: Option<usize>,
+    parse_method: Option<ParseMethod>,
+    trim: bool,
+    is_flag: bool,
+    is_optional: bool,
     use_default: bool,
 }
 
+struct AnyOrderSegment {
+    key: String,
+    is_required: bool,
+    capture: String,
+}
+
+#[derive(Clone, Copy)]
+enum ParseMethod {
+    Radix { radix: u32, prefix: &'static str },
+    Exp,
+}
+impl ParseMethod {
+    fn from_format_spec(format_spec: &str) -> Option<Self> {
+        let ps = FormatSpec::parse(format_spec).ok()?;
+        let (radix, prefix) = match ps.format_type {
+            FormatType::LowerHex | FormatType::UpperHex => (16, "0x"),
+            FormatType::Octal => (8, "0o"),
+            FormatType::Binary => (2, "0b"),
+            FormatType::LowerExp | FormatType::UpperExp => return Some(Self::Exp),
+            _ => return None,
+        };
+        let prefix = if ps.is_alternate { prefix } else { "" };
+        Some(Self::Radix { radix, prefix })
+    }
+
+    /// Other types are parsed by `FromStr` as before, since they may implement `LowerHex` etc. but not `FromStrRadix`.
+    ///
+    /// The inferred type `_` used by `parse!` is supported, because it is not a type written by the user.
+    fn is_supported_by(self, ty: &Type) -> bool {
+        if let Type::Infer(_) = ty {
+            return true;
+        }
+        matches!(
+            (self, NumberType::from_type(ty)),
+            (
+                Self::Radix { .. },
+                Some(NumberType::Signed | NumberType::Unsigned)
+            ) | (Self::Exp, Some(_))
+        )
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum NumberType {
+    Signed,
+    Unsigned,
+    Float,
+}
+impl NumberType {
+    fn from_type(ty: &Type) -> Option<Self> {
+        let ident = match ty {
+            Type::Path(ty) if ty.qself.is_none() => ty.path.get_ident()?.to_string(),
+            _ => return None,
+        };
+        Some(match ident.as_str() {
+            "i8" | "i16" | "i32" | "i64" | "i128" | "isize" => Self::Signed,
+            "u8" | "u16" | "u32" | "u64" | "u128" | "usize" => Self::Unsigned,
+            "f32" | "f64" => Self::Float,
+            _ => return None,
+        })
+    }
+}
+
 impl<'a> ParserBuilder<'a> {
-    fn new(source: &'a Fields) -> Result<Self> {
+    fn new(source: &'a Fields, generics: &'a GenericParamSet) -> Result<Self> {
         let mut fields = BTreeMap::new();
         for (key, field) in field_map(source) {
             fields.insert(key, FieldEntry::new(field)?);
         }
         Ok(Self {
             source,
+            generics,
             capture_next: 1,
             parse_format: ParseFormat::new(),
             fields,
             use_default: false,
             span: Span::call_site(),
             new_expr: None,
+            validate: None,
+            default_expr: None,
+            discriminant_capture: None,
+            any_order: None,
+            exprs: Vec::new(),
+            type_regexes: Vec::new(),
+            infer_regex: true,
+            whitespace: Whitespace::Exact,
+            greedy: false,
+            non_empty: false,
         })
     }
-    fn from_struct(hattrs: &HelperAttributes, data: &'a DataStruct) -> Result<Self> {
-        let mut s = Self::new(&data.fields)?;
-        let context = DisplayContext::Struct { data };
+    fn from_struct(
+        hattrs: &HelperAttributes,
+        data: &'a DataStruct,
+        generics: &'a GenericParamSet,
+    ) -> Result<Self> {
+        let mut s = Self::new(&data.fields, generics)?;
+        let context = DisplayContext::Struct {
+            data,
+            style: DisplayStyle::from_helper_attributes_struct(hattrs),
+            crate_path: &hattrs.crate_path,
+        };
         s.new_expr = hattrs.new_expr.clone();
+        s.validate = hattrs.validate.clone();
         s.apply_attrs(hattrs)?;
-        s.push_attrs(hattrs, &context)?;
+        if let Some(kv) = &hattrs.kv {
+            if let Some(span) = hattrs.span_of_from_str_format() {
+                bail!(
+                    span,
+                    "`#[display(kv)]` cannot be specified with `#[display(\"format\")]` or `#[from_str(regex = \"regex\")]`."
+                );
+            }
+            let format = DisplayFormat::from_kv(&data.fields, kv)?;
+            s.push_format_any_order(&format, &kv.sep, &context)?;
+        } else if let Some(sep) = &hattrs.any_order {
+            s.push_attrs_any_order(&[hattrs], &sep.value(), &context)?;
+        } else {
+            s.push_attrs(hattrs, &context)?;
+        }
         Ok(s)
     }
     fn from_variant(
         hattrs_variant: &HelperAttributes,
         hattrs_enum: &HelperAttributes,
         variant: &'a Variant,
+        discriminant: Option<&'a Discriminant>,
+        generics: &'a GenericParamSet,
     ) -> Result<Self> {
-        let mut s = Self::new(&variant.fields)?;
+        let mut s = Self::new(&variant.fields, generics)?;
         let style = DisplayStyle::from_helper_attributes(hattrs_enum, hattrs_variant);
-        let context = DisplayContext::Variant { variant, style };
+        let context = DisplayContext::Variant {
+            variant,
+            style,
+            discriminant,
+            crate_path: &hattrs_enum.crate_path,
+        };
         s.new_expr = hattrs_variant.new_expr.clone();
+        s.validate = hattrs_variant.validate.clone();
         s.apply_attrs(hattrs_enum)?;
         s.apply_attrs(hattrs_variant)?;
-        if !s.try_push_attrs(hattrs_variant, &context)? {
+        if let Some(sep) = &hattrs_variant.any_order {
+            s.push_attrs_any_order(&[hattrs_variant, hattrs_enum], &sep.value(), &context)?;
+        } else if !s.try_push_attrs(hattrs_variant, &context)? {
             s.push_attrs(hattrs_enum, &context)?;
         }
         Ok(s)
@@ -292,6 +935,7 @@ impl<'a> ParserBuilder<'a> {
     fn apply_attrs(&mut self, hattrs: &HelperAttributes) -> Result<()> {
         if hattrs.default_self.is_some() {
             self.use_default = true;
+            self.default_expr = hattrs.default_expr.clone();
         }
         for field in &hattrs.default_fields {
             let key = FieldKey::from_member(&field.0);
@@ -301,6 +945,18 @@ impl<'a> ParserBuilder<'a> {
         if let Some(span) = hattrs.span_of_from_str_format() {
             self.span = span;
         }
+        if let Some(infer_regex) = hattrs.infer_regex {
+            self.infer_regex = infer_regex;
+        }
+        if let Some((whitespace, _)) = hattrs.whitespace {
+            self.whitespace = whitespace;
+        }
+        self.greedy |= hattrs.greedy;
+        if hattrs.trim {
+            for field in self.fields.values_mut() {
+                field.trim = true;
+            }
+        }
         Ok(())
     }
     fn field(&mut self, key: &FieldKey, span: Span) -> Result<&mut FieldEntry<'a>> {
@@ -325,16 +981,25 @@ impl<'a> ParserBuilder<'a> {
             sub_keys = &keys[1..];
         }
         let field = field_of(&mut self.fields, field_key, span)?;
-        Ok(field.set_capture(sub_keys, &mut self.capture_next))
+        match field.set_capture(sub_keys, &mut self.capture_next) {
+            Some(c) => Ok(c),
+            None => bail!(
+                span,
+                "field `{}` cannot be specified more than once in format.",
+                join(std::iter::once(field_key).chain(sub_keys), ".")
+            ),
+        }
     }
 
     fn push_regex(&mut self, s: &LitStr, context: &DisplayContext) -> Result<()> {
         static REGEX_NUMBER: Lazy<Regex> = lazy_regex!("^[0-9]+$");
-        static REGEX_CAPTURE: Lazy<Regex> =
-            lazy_regex!(r"(?<esc>\\*)\(\?(?<p>P?)<(?<key>[_0-9a-zA-Z.]*)>");
+        static REGEX_CAPTURE: Lazy<Regex> = lazy_regex!(
+            r"(?<esc>\\*)\(\?(?<p>P?)<(?<key>[_0-9a-zA-Z.]*)(?::@(?<preset>[_0-9a-zA-Z]*))?>"
+        );
         const IDX_ESC: usize = 1;
         const IDX_P: usize = 2;
         const IDX_KEY: usize = 3;
+        const IDX_PRESET: usize = 4;
         fn is_escaped(s: &str) -> bool {
             s.len() % 2 == 1
         }
@@ -352,9 +1017,12 @@ impl<'a> ParserBuilder<'a> {
                 key.replace('.', "_")
             };
             let key = REGEX_NUMBER.replace(&key, "_$0");
-            format!("{esc}(?<{key}>")
+            let preset = c
+                .get(IDX_PRESET)
+                .map_or(Some(""), |m| regex_preset(m.as_str()));
+            format!("{esc}(?<{key}>{}", preset.unwrap_or_default())
         });
-        if let Err(e) = regex_syntax::ast::parse::Parser::new().parse(&text_debug) {
+        if let Err(e) = regex_syntax::Parser::new().parse(&text_debug) {
             bail!(s.span(), "{}", e)
         }
 
@@ -369,6 +1037,13 @@ impl<'a> ParserBuilder<'a> {
             has_capture = true;
             let cp = &c[IDX_P];
             let keys = FieldKey::from_str_deep(&c[IDX_KEY]);
+            let preset = match c.get(IDX_PRESET) {
+                Some(m) => match regex_preset(m.as_str()) {
+                    Some(preset) => preset,
+                    None => bail!(s.span(), "{}", unknown_regex_preset_message(m.as_str())),
+                },
+                None => "",
+            };
             let name = self.set_capture(context, &keys, s.span())?;
             if name == CAPTURE_NAME_EMPTY {
                 if !cp.is_empty() {
@@ -376,11 +1051,11 @@ impl<'a> ParserBuilder<'a> {
                 }
                 has_capture_empty = true;
             }
-            Ok(format!("{esc}(?<{name}>"))
+            Ok(format!("{esc}(?<{name}>{preset}"))
         })?;
 
         if has_capture_empty {
-            if let DisplayContext::Variant { variant, style } = context {
+            if let DisplayContext::Variant { variant, style, .. } = context {
                 let value = style.apply(&variant.ident);
                 self.parse_format
                     .push_hir(to_hir_with_expand(&text, CAPTURE_NAME_EMPTY, &value));
@@ -403,35 +1078,336 @@ impl<'a> ParserBuilder<'a> {
     }
     fn push_format(&mut self, format: &DisplayFormat, context: &DisplayContext) -> Result<()> {
         for p in &format.parts {
-            match p {
-                DisplayFormatPart::Str(s) => self.push_str(s),
-                DisplayFormatPart::EscapedBeginBracket => self.push_str("{"),
-                DisplayFormatPart::EscapedEndBracket => self.push_str("}"),
-                DisplayFormatPart::Var { arg, .. } => {
-                    let keys = FieldKey::from_str_deep(arg);
-                    if let DisplayContext::Variant { variant, style } = context {
-                        if keys.is_empty() {
-                            self.push_str(&style.apply(&variant.ident));
-                            continue;
-                        }
+            self.push_format_part(p, format, context)?;
+        }
+        Ok(())
+    }
+    fn push_format_part(
+        &mut self,
+        part: &DisplayFormatPart,
+        format: &DisplayFormat,
+        context: &DisplayContext,
+    ) -> Result<()> {
+        let span = format.span;
+        match part {
+            DisplayFormatPart::Str(s) => self.push_text(s),
+            DisplayFormatPart::EscapedBeginBracket => self.push_str("{"),
+            DisplayFormatPart::EscapedEndBracket => self.push_str("}"),
+            DisplayFormatPart::Cond { arg, text } => self.push_cond(context, arg, text, span)?,
+            DisplayFormatPart::FieldName { arg } => self.push_str(&context.field_name(arg, span)?),
+            DisplayFormatPart::Var { arg, format_spec } => {
+                if let Some(expr) = format.arg_expr(arg)? {
+                    self.push_expr(expr, format_spec);
+                    return Ok(());
+                }
+                let ps = FormatSpec::parse(format_spec).unwrap_or_default();
+                let fill = ps.width.as_ref().map(|_| ps.fill.unwrap_or(' '));
+                let (fill_start, fill_end) = match ps.align {
+                    Some(Align::Left) => (None, fill),
+                    Some(Align::Right) => (fill, None),
+                    Some(Align::Center) | None => (fill, fill),
+                };
+                // The fill is limited to be shorter than the width, so that the value is not matched as the fill.
+                // If the width is not known or the value can be on either side, the value is required to be non-empty instead.
+                let (max_start, max_end) = match (&ps.width, &ps.align) {
+                    (Some(SubArg::Value(width)), Some(Align::Center)) => {
+                        (Some(width.saturating_sub(1) / 2), Some(width / 2))
+                    }
+                    (Some(SubArg::Value(width)), _) => {
+                        (Some(width.saturating_sub(1)), Some(width.saturating_sub(1)))
+                    }
+                    _ => (None, None),
+                };
+                let non_empty = self.non_empty;
+                self.non_empty |= fill.is_some() && (max_start.is_none() || ps.align.is_none());
+                self.push_fill(fill_start, max_start);
+                let result = self.push_var(arg, format_spec, span, context);
+                self.non_empty = non_empty;
+                result?;
+                self.push_fill(fill_end, max_end);
+            }
+        }
+        Ok(())
+    }
+    fn push_fill(&mut self, fill: Option<char>, max: Option<usize>) {
+        if let Some(fill) = fill {
+            let fill = regex_syntax::escape(&fill.to_string());
+            let max = max.map_or(String::new(), |max| max.to_string());
+            self.parse_format
+                .push_hir(to_hir(&format!("(?:{fill}){{0,{max}}}")));
+        }
+    }
+    fn set_parse_method(
+        &mut self,
+        context: &DisplayContext,
+        keys: &[FieldKey],
+        format_spec: &str,
+        span: Span,
+    ) -> Result<()> {
+        let key = match (context, keys) {
+            (DisplayContext::Field { key, .. }, []) => *key,
+            (DisplayContext::Struct { .. } | DisplayContext::Variant { .. }, [key]) => key,
+            _ => return Ok(()),
+        };
+        if let Some(method) = ParseMethod::from_format_spec(format_spec) {
+            let field = self.field(key, span)?;
+            if method.is_supported_by(&field.source.ty) {
+                field.parse_method = Some(method);
+            }
+        }
+        Ok(())
+    }
+    fn push_var(
+        &mut self,
+        arg: &str,
+        format_spec: &str,
+        span: Span,
+        context: &DisplayContext,
+    ) -> Result<()> {
+        let keys = FieldKey::from_str_deep(arg);
+        if let DisplayContext::Variant {
+            variant,
+            style,
+            discriminant,
+            ..
+        } = context
+        {
+            if keys.is_empty() {
+                self.push_str(&style.apply(&variant.ident));
+                return Ok(());
+            }
+            if let Some(discriminant) = discriminant {
+                if arg == DISCRIMINANT_ARG {
+                    return self.push_discriminant(discriminant, format_spec, span);
+                }
+            }
+        }
+        self.set_parse_method(context, &keys, format_spec, span)?;
+        if keys.len() == 1 {
+            return self.push_field(context, &keys[0], span);
+        }
+        let regex = match context {
+            DisplayContext::Field { key, .. } if keys.is_empty() => self.field_regex(key, span)?,
+            _ => any_regex(self.greedy, self.non_empty).into(),
+        };
+        let c = self.set_capture(context, &keys, span)?;
+        self.parse_format
+            .push_hir(to_hir(&format!("(?<{c}>{regex})")));
+        Ok(())
+    }
+    fn field_regex(&mut self, key: &FieldKey, span: Span) -> Result<String> {
+        let infer_regex = self.infer_regex;
+        let greedy = self.greedy;
+        let non_empty = self.non_empty;
+        let generics = self.generics;
+        let field = self.field(key, span)?;
+        let infer_regex = field.hattrs.infer_regex.unwrap_or(infer_regex);
+        let regex = if let Some(quote) = field.hattrs.quote {
+            let q = regex_syntax::escape(&quote.to_string());
+            format!(r"(?s:{q}(?:[^{q}\\]|\\.)*{q})")
+        } else if let Some(regex) = infer_regex
+            .then(|| type_regex(&field.source.ty, field.parse_method))
+            .flatten()
+        {
+            regex
+        } else {
+            let regex = any_regex(greedy || field.hattrs.greedy, non_empty).into();
+            let ty = &field.source.ty;
+            if !infer_regex || field.parse_method.is_some() || !may_be_unit_enum(ty, generics) {
+                return Ok(regex);
+            }
+            // The type may be an enum that implements `FromStrRegex`, which is checked at runtime.
+            let (ty, trim) = (ty.clone(), field.trim);
+            let c = capture_name(self.capture_next);
+            self.capture_next += 1;
+            let placeholder = format!("(?<{c}>)");
+            self.type_regexes.push((c, ty, trim, regex));
+            return Ok(placeholder);
+        };
+        // Whitespace to be trimmed is not matched by the regexes above, so it is matched here.
+        Ok(if field.trim {
+            format!(r"\s*(?:{regex})\s*")
+        } else {
+            regex
+        })
+    }
+    fn push_attrs_any_order(
+        &mut self,
+        hattrs_list: &[&HelperAttributes],
+        sep: &str,
+        context: &DisplayContext,
+    ) -> Result<()> {
+        for hattrs in hattrs_list {
+            if let Some(regex) = &hattrs.regex {
+                bail!(
+                    regex.span(),
+                    "`#[from_str(any_order)]` cannot be specified with `#[from_str(regex = \"regex\")]`."
+                );
+            }
+            if let Some(format) = &hattrs.format {
+                return self.push_format_any_order(format, sep, context);
+            }
+        }
+        self.push_format_any_order(&context.default_from_str_format()?, sep, context)
+    }
+    fn push_format_any_order(
+        &mut self,
+        format: &DisplayFormat,
+        sep: &str,
+        context: &DisplayContext,
+    ) -> Result<()> {
+        let mut segments = Vec::new();
+        for p in &format.parts {
+            if let DisplayFormatPart::Str(s) = p {
+                for (index, text) in s.split(sep).enumerate() {
+                    if index != 0 {
+                        segments.extend(self.parse_format.take_segment());
                     }
-                    if keys.len() == 1 {
-                        self.push_field(context, &keys[0], format.span)?;
-                        continue;
+                    if !text.is_empty() {
+                        self.push_text(text);
                     }
-                    let c = self.set_capture(context, &keys, format.span)?;
-                    self.parse_format
-                        .push_hir(to_hir(&format!("(?<{c}>(?s:.*?))")));
                 }
+            } else {
+                self.push_format_part(p, format, context)?;
+            }
+        }
+        segments.extend(self.parse_format.take_segment());
+        let mut keys: Vec<AnyOrderSegment> = Vec::new();
+        for segment in &segments {
+            let key = leading_literal(segment);
+            if key.is_empty() {
+                bail!(
+                    format.span,
+                    "`#[from_str(any_order)]` requires each segment to start with fixed text."
+                );
             }
+            if let Some(other) = keys
+                .iter()
+                .find(|other| other.key.starts_with(&key) || key.starts_with(&other.key))
+            {
+                bail!(
+                    format.span,
+                    "`#[from_str(any_order)]` cannot distinguish segments starting with `{}` and `{}`.",
+                    other.key,
+                    key
+                );
+            }
+            keys.push(AnyOrderSegment {
+                key,
+                is_required: segment.properties().explicit_captures_len() == 0,
+                capture: capture_name(self.capture_next),
+            });
+            self.capture_next += 1;
+        }
+        let segments = segments
+            .into_iter()
+            .zip(&keys)
+            .map(|(segment, key)| {
+                Hir::capture(Capture {
+                    index: 0,
+                    name: Some(key.capture.clone().into()),
+                    sub: Box::new(segment),
+                })
+            })
+            .collect();
+        self.parse_format.push_any_order(segments, sep);
+        self.any_order = Some((sep.to_string(), keys));
+        for field in self.fields.values_mut() {
+            field.is_optional = true;
         }
         Ok(())
     }
     fn push_str(&mut self, string: &str) {
         self.parse_format.push_str(string)
     }
+    fn push_text(&mut self, text: &str) {
+        match self.whitespace {
+            Whitespace::Exact => self.push_str(text),
+            Whitespace::Flexible => {
+                let words: Vec<_> = text.split_whitespace().map(regex_syntax::escape).collect();
+                let regex = if words.is_empty() {
+                    r"\s+".into()
+                } else {
+                    format!(r"\s*{}\s*", words.join(r"\s+"))
+                };
+                self.parse_format.push_hir(to_hir(&regex));
+            }
+        }
+    }
+    fn push_cond(
+        &mut self,
+        context: &DisplayContext,
+        arg: &str,
+        text: &str,
+        span: Span,
+    ) -> Result<()> {
+        let keys = FieldKey::from_str_deep(arg);
+        let (field_key, sub_keys) = if let DisplayContext::Field { key, .. } = context {
+            (*key, &keys[..])
+        } else if let Some(field_key) = keys.first() {
+            (field_key, &keys[1..])
+        } else {
+            bail!(span, "field name is required in conditional format.");
+        };
+        if !sub_keys.is_empty() {
+            bail!(
+                span,
+                "field chain cannot be used in conditional format with `FromStr`."
+            );
+        }
+        let field = field_of(&mut self.fields, field_key, span)?;
+        field.is_flag = true;
+        let c = match field.set_capture(&[], &mut self.capture_next) {
+            Some(c) => c,
+            None => bail!(
+                span,
+                "field `{field_key}` cannot be specified more than once in format."
+            ),
+        };
+        let text = regex_syntax::escape(text);
+        self.parse_format
+            .push_hir(to_hir(&format!("(?<{c}>{text})?")));
+        Ok(())
+    }
+    fn push_discriminant(
+        &mut self,
+        discriminant: &Discriminant,
+        format_spec: &str,
+        span: Span,
+    ) -> Result<()> {
+        if let (Some(value), true) = (discriminant.value, format_spec.is_empty()) {
+            self.push_str(&value.to_string());
+            return Ok(());
+        }
+        if self.discriminant_capture.is_some() {
+            bail!(
+                span,
+                "`{DISCRIMINANT_ARG}` cannot be specified more than once in format."
+            );
+        }
+        let idx = self.capture_next;
+        self.capture_next += 1;
+        let parse_method = ParseMethod::from_format_spec(format_spec);
+        self.discriminant_capture = Some((idx, discriminant.ty.clone(), parse_method));
+        let c = capture_name(idx);
+        self.parse_format
+            .push_hir(to_hir(&format!("(?<{c}>(?s:.*?))")));
+        Ok(())
+    }
+    fn push_expr(&mut self, expr: Expr, format_spec: &str) {
+        let c = capture_name(self.capture_next);
+        self.capture_next += 1;
+        self.parse_format.push_hir(to_hir(&format!("(?<{c}>)")));
+        self.exprs.push((c, expr, format!("{{:{format_spec}}}")));
+    }
     fn push_field(&mut self, context: &DisplayContext, key: &FieldKey, span: Span) -> Result<()> {
         let e = self.field(key, span)?;
+        if e.capture.is_some() {
+            bail!(
+                span,
+                "field `{key}` cannot be specified more than once in format."
+            );
+        }
         let hattrs = e.hattrs.clone();
         let parent = context;
         let field = e.source;
@@ -461,7 +1437,7 @@ impl<'a> ParserBuilder<'a> {
 
     fn build_from_str_body(&self, crate_path: &Path, constructor: Path) -> Result<TokenStream> {
         let code = self.build_parse_code(crate_path, constructor)?;
-        Ok(quote! {
+        Ok(quote_hygienic! {
             #code
             ::core::result::Result::Err(#crate_path::ParseError::new())
         })
@@ -473,9 +1449,9 @@ impl<'a> ParserBuilder<'a> {
     ) -> Result<ParseVariantCode> {
         match &self.parse_format {
             ParseFormat::Hirs(_) => {
-                let fn_ident: Ident = format_ident!("parse_variant");
+                let fn_ident = Ident::new("parse_variant", Span::mixed_site());
                 let code = self.build_from_str_body(crate_path, constructor)?;
-                let code = quote! {
+                let code = quote_hygienic! {
                     let #fn_ident = |s: &str| -> ::core::result::Result<Self, #crate_path::ParseError> {
                         #code
                     };
@@ -487,7 +1463,7 @@ impl<'a> ParserBuilder<'a> {
             }
             ParseFormat::String(s) => {
                 let code = self.build_construct_code(crate_path, constructor)?;
-                let code = quote! { #s  => { #code }};
+                let code = quote_hygienic! { #s  => { #code }};
                 Ok(ParseVariantCode::MatchArm(code))
             }
         }
@@ -498,7 +1474,10 @@ impl<'a> ParserBuilder<'a> {
         let re;
         match &self.parse_format {
             ParseFormat::Hirs(hirs) => {
-                re = Regex::new(&to_regex_string(hirs)).unwrap();
+                re = match Regex::new(&to_regex_string(hirs)) {
+                    Ok(re) => re,
+                    Err(e) => bail!(self.span, "{}", e),
+                };
                 for (index, name) in re.capture_names().enumerate() {
                     if let Some(name) = name {
                         names.insert(name, index);
@@ -508,71 +1487,234 @@ impl<'a> ParserBuilder<'a> {
             ParseFormat::String(_) => {}
         }
 
+        let use_vars = self
+            .fields
+            .values()
+            .any(|field| field.is_default_expr_used());
+        let return_value = self.build_return_value(crate_path);
         let code = if let Some(new_expr) = &self.new_expr {
-            let mut code = TokenStream::new();
-            for (key, field) in &self.fields {
-                let expr = field.build_field_init_expr(crate_path, &names, key, self.span)?;
-                let var = key.new_arg_var();
-                code.extend(quote! { let #var = #expr; });
-            }
-            code.extend(quote! {
+            let mut code = self.build_field_vars(crate_path, &names, false)?;
+            code.extend(quote_hygienic! {
                 if let ::core::result::Result::Ok(value) = #crate_path::IntoResult::into_result(#new_expr) {
-                    return ::core::result::Result::Ok(value);
+                    #return_value
                 }
             });
             code
         } else if self.use_default {
+            let mut vars = TokenStream::new();
             let mut setters = Vec::new();
+            if use_vars {
+                vars = self.build_field_vars(crate_path, &names, true)?;
+            }
             for (key, field) in &self.fields {
-                let left_expr = quote! { value . #key };
-                setters.push(field.build_setters(crate_path, &names, key, left_expr, true));
+                let left_expr = quote_hygienic! { value . #key };
+                if use_vars {
+                    let needs_presence_check = field.needs_presence_check();
+                    if !needs_presence_check && field.build_expr(crate_path, &names, key).is_some()
+                    {
+                        let var = key.new_arg_var();
+                        setters.push(quote_hygienic! { #left_expr = #var; });
+                    }
+                    setters.push(field.build_setters(
+                        crate_path,
+                        &names,
+                        key,
+                        left_expr,
+                        needs_presence_check,
+                    ));
+                } else {
+                    setters.push(field.build_setters(crate_path, &names, key, left_expr, true));
+                }
             }
-            quote! {
-                let mut value = <Self as ::core::default::Default>::default();
+            let default_expr = if let Some(expr) = &self.default_expr {
+                quote_hygienic! { #expr }
+            } else {
+                quote_hygienic! { <Self as ::core::default::Default>::default() }
+            };
+            quote_hygienic! {
+                #vars
+                let mut value = #default_expr;
                 #(#setters)*
-                return ::core::result::Result::Ok(value);
+                #return_value
             }
         } else {
+            let mut vars = TokenStream::new();
+            if use_vars {
+                vars = self.build_field_vars(crate_path, &names, false)?;
+            }
+            let mut exprs = Vec::new();
+            for (key, field) in &self.fields {
+                exprs.push(if use_vars {
+                    let var = key.new_arg_var();
+                    quote_hygienic! { #var }
+                } else {
+                    field.build_field_init_expr(crate_path, &names, key, self.span)?
+                });
+            }
             let ps = match &self.source {
                 Fields::Named(..) => {
-                    let mut fields_code = Vec::new();
-                    for (key, field) in &self.fields {
-                        let expr =
-                            field.build_field_init_expr(crate_path, &names, key, self.span)?;
-                        fields_code.push(quote! { #key : #expr })
-                    }
-                    quote! { { #(#fields_code,)* } }
+                    let keys = self.fields.keys();
+                    quote_hygienic! { { #(#keys : #exprs,)* } }
                 }
-                Fields::Unnamed(..) => {
-                    let mut fields_code = Vec::new();
-                    for (key, field) in &self.fields {
-                        fields_code
-                            .push(field.build_field_init_expr(crate_path, &names, key, self.span)?);
-                    }
-                    quote! { ( #(#fields_code,)* ) }
-                }
-                Fields::Unit => quote! {},
+                Fields::Unnamed(..) => quote_hygienic! { ( #(#exprs,)* ) },
+                Fields::Unit => quote_hygienic! {},
             };
-            quote! { return ::core::result::Result::Ok(#constructor #ps); }
+            if self.validate.is_some() {
+                quote_hygienic! {
+                    #vars
+                    let value = #constructor #ps;
+                    #return_value
+                }
+            } else {
+                quote_hygienic! {
+                    #vars
+                    return ::core::result::Result::Ok(#constructor #ps);
+                }
+            }
         };
         Ok(code)
     }
+    fn build_dump_notes(&self) -> String {
+        let hirs = match &self.parse_format {
+            ParseFormat::String(s) => return format!("string: {s:?}\n"),
+            ParseFormat::Hirs(hirs) => hirs,
+        };
+        let regex = to_regex_string(hirs);
+        let mut captures = HashMap::new();
+        for (key, field) in &self.fields {
+            if let Some(idx) = field.capture {
+                captures.insert(capture_name(idx), format!("field `{key}`"));
+            }
+            for (keys, idx) in &field.deep_captures {
+                captures.insert(
+                    capture_name(*idx),
+                    format!("field `{key}.{}`", join(keys, ".")),
+                );
+            }
+        }
+        if let Some((idx, ..)) = &self.discriminant_capture {
+            captures.insert(capture_name(*idx), "discriminant".into());
+        }
+        if let Some((_, segments)) = &self.any_order {
+            for segment in segments {
+                captures.insert(
+                    segment.capture.clone(),
+                    format!("segment `{}`", segment.key),
+                );
+            }
+        }
+        for (name, expr, _) in &self.exprs {
+            captures.insert(
+                name.clone(),
+                format!("format argument `{}` (replaced at runtime)", quote!(#expr)),
+            );
+        }
+        for (name, ty, ..) in &self.type_regexes {
+            captures.insert(
+                name.clone(),
+                format!("regex of type `{}` (replaced at runtime)", quote!(#ty)),
+            );
+        }
+        let mut notes = format!("regex: {regex}\ncaptures:\n");
+        if let Ok(re) = Regex::new(&regex) {
+            for name in re.capture_names().flatten() {
+                let desc = captures
+                    .get(name)
+                    .map_or("(not used)", |desc| desc.as_str());
+                notes.push_str(&format!("  {name} => {desc}\n"));
+            }
+        }
+        notes
+    }
+    fn is_infallible(&self) -> bool {
+        self.new_expr.is_none()
+            && self.validate.is_none()
+            && self.discriminant_capture.is_none()
+            && self.exprs.is_empty()
+            && self.fields.values().all(|field| field.is_infallible())
+    }
+    fn build_return_value(&self, crate_path: &Path) -> TokenStream {
+        let mut code = TokenStream::new();
+        if let Some(validate) = &self.validate {
+            code.extend(quote_hygienic! {
+                if let ::core::result::Result::Err(e) = #validate(&value) {
+                    return ::core::result::Result::Err(#crate_path::ParseError::from_error(e));
+                }
+            });
+        }
+        code.extend(quote_hygienic! { return ::core::result::Result::Ok(value); });
+        code
+    }
+    fn build_field_vars(
+        &self,
+        crate_path: &Path,
+        names: &HashMap<&str, usize>,
+        skip_missing: bool,
+    ) -> Result<TokenStream> {
+        let mut code = TokenStream::new();
+        let mut default_exprs = Vec::new();
+        for (key, field) in &self.fields {
+            if skip_missing
+                && (field.needs_presence_check()
+                    || field.build_expr(crate_path, names, key).is_none())
+            {
+                continue;
+            }
+            let expr = field.build_field_init_expr(crate_path, names, key, self.span)?;
+            let var = key.new_arg_var();
+            if field.is_default_expr_used() {
+                let index = self
+                    .source
+                    .iter()
+                    .position(|f| std::ptr::eq(f, field.source));
+                default_exprs.push((index, quote_hygienic! { let #var = #expr; }));
+            } else {
+                code.extend(quote_hygienic! { let #var = #expr; });
+            }
+        }
+        default_exprs.sort_by_key(|(index, _)| *index);
+        code.extend(default_exprs.into_iter().map(|(_, code)| code));
+        Ok(code)
+    }
     fn build_parse_code(&self, crate_path: &Path, constructor: Path) -> Result<TokenStream> {
-        let code = self.build_construct_code(crate_path, constructor)?;
+        let mut code = self.build_construct_code(crate_path, constructor.clone())?;
         let code = match &self.parse_format {
             ParseFormat::Hirs(hirs) => {
                 let regex = to_regex_string(hirs);
-                quote! {
-                    #[allow(clippy::trivial_regex)]
-                    static RE: #crate_path::helpers::once_cell::sync::Lazy<#crate_path::helpers::regex::Regex> =
-                        #crate_path::helpers::once_cell::sync::Lazy::new(|| #crate_path::helpers::regex::Regex::new(#regex).unwrap());
-                    if let Some(c) = RE.captures(&s) {
+                if let Some((idx, ty, parse_method)) = &self.discriminant_capture {
+                    let re = Regex::new(&regex).unwrap();
+                    let name = capture_name(*idx);
+                    let index = re.capture_names().position(|n| n == Some(&name)).unwrap();
+                    let s = quote_hygienic!(c.get(#index).map_or("", |m| m.as_str()));
+                    let value = match parse_method {
+                        None => quote_hygienic!(#s.parse::<#ty>()),
+                        Some(ParseMethod::Radix { radix, prefix }) => quote_hygienic! {
+                            <#ty as #crate_path::helpers::FromStrRadix>::from_str_radix(#s, #radix, #prefix)
+                        },
+                        Some(ParseMethod::Exp) => quote_hygienic! {
+                            <#ty as #crate_path::helpers::FromStrExp>::from_str_exp(#s)
+                        },
+                    };
+                    code = quote_hygienic! {
+                        if #value.ok() == ::core::option::Option::Some(#constructor as #ty) {
+                            #code
+                        }
+                    };
+                }
+                if let Some((sep, segments)) = &self.any_order {
+                    let re = Regex::new(&regex).unwrap();
+                    code = build_any_order_check(crate_path, &re, sep, segments, code);
+                }
+                let re = self.build_regex_var(crate_path, &regex);
+                quote_hygienic! {
+                    #re
+                    if let Some(c) = re.captures(&s) {
                          #code
                     }
                 }
             }
             ParseFormat::String(s) => {
-                quote! {
+                quote_hygienic! {
                     if s == #s {
                         #code
                     }
@@ -582,6 +1724,112 @@ impl<'a> ParserBuilder<'a> {
         Ok(code)
     }
 
+    /// Builds a statement that declares `re`, the regex to match.
+    fn build_regex_var(&self, crate_path: &Path, regex: &str) -> TokenStream {
+        if !self.exprs.is_empty() {
+            let parts = self.build_regex_parts(crate_path, regex);
+            return quote_hygienic! {
+                let re = {
+                    static RE: #crate_path::helpers::RegexCache = #crate_path::helpers::RegexCache::new();
+                    RE.get(&[#(#parts),*])
+                };
+            };
+        }
+        if !self.type_regexes.is_empty() {
+            let parts = self.build_regex_parts(crate_path, regex);
+            return quote_hygienic! {
+                let re: &#crate_path::helpers::regex::Regex = {
+                    static RE: #crate_path::helpers::once_cell::sync::Lazy<#crate_path::helpers::regex::Regex> =
+                        #crate_path::helpers::once_cell::sync::Lazy::new(|| #crate_path::helpers::regex::Regex::new(&[#(#parts),*].concat()).unwrap());
+                    &RE
+                };
+            };
+        }
+        quote_hygienic! {
+            let re: &#crate_path::helpers::regex::Regex = {
+                #[allow(clippy::trivial_regex)]
+                static RE: #crate_path::helpers::once_cell::sync::Lazy<#crate_path::helpers::regex::Regex> =
+                    #crate_path::helpers::once_cell::sync::Lazy::new(|| #crate_path::helpers::regex::Regex::new(#regex).unwrap());
+                &RE
+            };
+        }
+    }
+
+    /// Builds an expression that parses the field `key` from `s`, the text of the field.
+    fn build_field_parse_expr(
+        &self,
+        crate_path: &Path,
+        key: &FieldKey,
+        span: Span,
+    ) -> Result<TokenStream> {
+        let regex = match &self.parse_format {
+            ParseFormat::Hirs(hirs) => to_regex_string(hirs),
+            ParseFormat::String(s) => format!("^{}$", regex_syntax::escape(s)),
+        };
+        let re = match Regex::new(&regex) {
+            Ok(re) => re,
+            Err(e) => bail!(self.span, "{}", e),
+        };
+        let mut names = HashMap::new();
+        for (index, name) in re.capture_names().enumerate() {
+            if let Some(name) = name {
+                names.insert(name, index);
+            }
+        }
+        let expr = self.fields[key].build_field_init_expr(crate_path, &names, key, span)?;
+        let re = self.build_regex_var(crate_path, &regex);
+        let msg = format!("field `{key}` parse failed.");
+        Ok(quote_hygienic! {
+            {
+                #re
+                match re.captures(s) {
+                    ::core::option::Option::Some(c) => #expr,
+                    ::core::option::Option::None => {
+                        return ::core::result::Result::Err(#crate_path::ParseError::with_message(#msg))
+                    }
+                }
+            }
+        })
+    }
+
+    fn build_regex_parts(&self, crate_path: &Path, mut regex: &str) -> Vec<TokenStream> {
+        let mut values = Vec::new();
+        for (c, expr, format_str) in &self.exprs {
+            values.push((c, quote_hygienic! {
+                #crate_path::helpers::escape_display(::core::format_args!(#format_str, #expr)).as_str()
+            }));
+        }
+        for (c, ty, trim, default) in &self.type_regexes {
+            values.push((
+                c,
+                quote_hygienic! {
+                    #crate_path::helpers::type_regex(
+                        {
+                            use #crate_path::helpers::TypeRegexDefault as _;
+                            #crate_path::helpers::TypeRegex::<#ty>::new().get()
+                        },
+                        #trim,
+                        #default,
+                    ).as_str()
+                },
+            ));
+        }
+        let placeholder = |c: &str| format!("(?P<{c}>(?:))");
+        values.sort_by_key(|(c, _)| regex.find(&placeholder(c)));
+        let mut parts = Vec::new();
+        for (c, value) in values {
+            let placeholder = placeholder(c);
+            let index = regex.find(&placeholder).unwrap();
+            let head = format!("{}(?P<{c}>", &regex[..index]);
+            parts.push(quote_hygienic!(#head));
+            parts.push(value);
+            parts.push(quote_hygienic!(")"));
+            regex = &regex[index + placeholder.len()..];
+        }
+        parts.push(quote_hygienic!(#regex));
+        parts
+    }
+
     fn build_bounds(&self, generics: &GenericParamSet, bounds: &mut Bounds) {
         if !bounds.can_extend {
             return;
@@ -600,37 +1848,57 @@ impl<'a> ParserBuilder<'a> {
 impl<'a> FieldEntry<'a> {
     fn new(source: &'a Field) -> Result<Self> {
         let hattrs = HelperAttributes::from(&source.attrs)?;
+        hattrs.check_kv_not_specified()?;
+        if let Some((_, span)) = hattrs.whitespace {
+            bail!(
+                span,
+                "`#[from_str(whitespace = \"...\")]` cannot be specified for field."
+            );
+        }
+        if let Some((_, span)) = hattrs.priority {
+            bail!(
+                span,
+                "`#[from_str(priority = ...)]` cannot be specified for field."
+            );
+        }
+        if let Some((_, span)) = hattrs.match_policy {
+            bail!(
+                span,
+                "`#[from_str(match = \"...\")]` cannot be specified for field."
+            );
+        }
         let use_default = hattrs.default_self.is_some();
+        let trim = hattrs.trim;
         Ok(Self {
+            trim,
             hattrs,
             deep_captures: BTreeMap::new(),
             capture: None,
+            parse_method: None,
+            is_flag: false,
+            is_optional: false,
             use_default,
             source,
         })
     }
-    #[allow(clippy::collapsible_else_if)]
-    fn set_capture(&mut self, keys: &[FieldKey], capture_next: &mut usize) -> String {
-        let idx = if keys.is_empty() {
-            if let Some(idx) = self.capture {
-                idx
-            } else {
-                let idx = *capture_next;
-                self.capture = Some(idx);
-                *capture_next += 1;
-                idx
-            }
+    /// Returns `None` if the capture is already used, since a capture name cannot be used twice in a regex.
+    fn set_capture(&mut self, keys: &[FieldKey], capture_next: &mut usize) -> Option<String> {
+        let is_used = if keys.is_empty() {
+            self.capture.is_some()
         } else {
-            if let Some(&idx) = self.deep_captures.get(keys) {
-                idx
-            } else {
-                let idx = *capture_next;
-                self.deep_captures.insert(keys.to_vec(), idx);
-                *capture_next += 1;
-                idx
-            }
+            self.deep_captures.contains_key(keys)
         };
-        capture_name(idx)
+        if is_used {
+            return None;
+        }
+        let idx = *capture_next;
+        *capture_next += 1;
+        if keys.is_empty() {
+            self.capture = Some(idx);
+        } else {
+            self.deep_captures.insert(keys.to_vec(), idx);
+        }
+        Some(capture_name(idx))
     }
     fn capture_index(&self, names: &HashMap<&str, usize>) -> Option<usize> {
         Some(capture_index(self.capture?, names))
@@ -642,17 +1910,61 @@ impl<'a> FieldEntry<'a> {
         key: &FieldKey,
     ) -> Option<TokenStream> {
         if let Some(capture_index) = self.capture_index(names) {
-            Some(build_parse_capture_expr(
+            if self.is_flag {
+                return Some(quote_hygienic! { c.get(#capture_index).is_some() });
+            }
+            let expr = build_parse_capture_expr(
                 crate_path,
                 &key.to_string(),
                 capture_index,
-            ))
+                self.parse_method,
+                self.trim,
+                self.hattrs.quote,
+            );
+            if !self.is_optional {
+                return Some(expr);
+            }
+            let missing = if self.use_default {
+                self.build_default_expr()
+            } else {
+                let msg = format!("field `{key}` is missing.");
+                quote_hygienic! {
+                    return ::core::result::Result::Err(#crate_path::ParseError::with_message(#msg))
+                }
+            };
+            Some(quote_hygienic! {
+                if c.get(#capture_index).is_some() { #expr } else { #missing }
+            })
         } else if self.use_default {
-            Some(quote! { ::core::default::Default::default() })
+            Some(self.build_default_expr())
         } else {
             None
         }
     }
+    fn build_default_expr(&self) -> TokenStream {
+        if let Some(expr) = &self.hattrs.default_expr {
+            quote_hygienic! { #expr }
+        } else {
+            quote_hygienic! { ::core::default::Default::default() }
+        }
+    }
+    fn is_infallible(&self) -> bool {
+        if !self.deep_captures.is_empty() {
+            return false;
+        }
+        if self.capture.is_none() || self.is_flag {
+            return true;
+        }
+        (!self.is_optional || self.use_default)
+            && self.parse_method.is_none()
+            && matches!(&self.source.ty, Type::Path(ty) if ty.qself.is_none() && ty.path.is_ident("String"))
+    }
+    fn needs_presence_check(&self) -> bool {
+        self.is_optional && !self.is_flag && !self.use_default && self.capture.is_some()
+    }
+    fn is_default_expr_used(&self) -> bool {
+        self.capture.is_none() && self.use_default && self.hattrs.default_expr.is_some()
+    }
     fn build_setters(
         &self,
         crate_path: &Path,
@@ -662,18 +1974,39 @@ impl<'a> FieldEntry<'a> {
         include_self: bool,
     ) -> TokenStream {
         let mut setters = Vec::new();
-        if include_self {
+        if include_self && self.needs_presence_check() {
+            let capture_index = self.capture_index(names).unwrap();
+            let expr = build_parse_capture_expr(
+                crate_path,
+                &key.to_string(),
+                capture_index,
+                self.parse_method,
+                self.trim,
+                self.hattrs.quote,
+            );
+            setters.push(quote_hygienic! {
+                if c.get(#capture_index).is_some() {
+                    #left_expr = #expr;
+                }
+            });
+        } else if include_self {
             if let Some(expr) = self.build_expr(crate_path, names, key) {
-                setters.push(quote! { #left_expr = #expr; });
+                setters.push(quote_hygienic! { #left_expr = #expr; });
             }
         }
         for (keys, idx) in &self.deep_captures {
             let field_name = key.to_string() + &join(keys, ".");
-            let expr =
-                build_parse_capture_expr(crate_path, &field_name, capture_index(*idx, names));
-            setters.push(quote! { #left_expr #(.#keys)* = #expr; });
+            let expr = build_parse_capture_expr(
+                crate_path,
+                &field_name,
+                capture_index(*idx, names),
+                None,
+                self.trim,
+                None,
+            );
+            setters.push(quote_hygienic! { #left_expr #(.#keys)* = #expr; });
         }
-        quote! { #(#setters)* }
+        quote_hygienic! { #(#setters)* }
     }
 
     fn build_field_init_expr(
@@ -686,9 +2019,9 @@ impl<'a> FieldEntry<'a> {
         if let Some(mut expr) = self.build_expr(crate_path, names, key) {
             if !self.deep_captures.is_empty() {
                 let setters =
-                    self.build_setters(crate_path, names, key, quote!(field_value), false);
+                    self.build_setters(crate_path, names, key, quote_hygienic!(field_value), false);
                 let ty = &self.source.ty;
-                expr = quote! {
+                expr = quote_hygienic! {
                     {
                         let mut field_value : #ty = #expr;
                         #setters
@@ -720,10 +2053,80 @@ struct DisplayArgs {
     #[struct_meta(unnamed)]
     format: Option<LitStr>,
     style: Option<LitStr>,
+    digits: Option<LitStr>,
     bound: Option<Vec<Quotable<Bound>>>,
     #[struct_meta(name = "crate")]
     crate_path: Option<Path>,
-    dump: bool,
+    discriminant: Flag,
+    kv: Option<NameArgs<Option<KeyValueArgs>>>,
+    dump: Option<NameArgs<Option<Ident>>>,
+    quote: Flag,
+    args: HashMap<String, NameValue<Expr>>,
+}
+
+struct DisplayArgsWithQuote {
+    args: DisplayArgs,
+    quote: Option<LitStr>,
+}
+impl Parse for DisplayArgsWithQuote {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut tokens = TokenStream::new();
+        let mut quote = None;
+        while !input.is_empty() {
+            if input.peek(LitStr) {
+                let format: LitStr = input.parse()?;
+                tokens.extend(quote!(#format));
+            } else {
+                let name = Ident::parse_any(input)?;
+                if input.peek(Token![=]) {
+                    let eq: Token![=] = input.parse()?;
+                    let value: Expr = input.parse()?;
+                    if name == "quote" {
+                        if quote.is_some() {
+                            bail!(name.span(), "parameter `quote` specified more than once");
+                        }
+                        quote = Some(syn::parse2(quote!(#value))?);
+                    } else {
+                        tokens.extend(quote!(#name #eq #value));
+                    }
+                } else if input.peek(syn::token::Paren) {
+                    let args: proc_macro2::TokenTree = input.parse()?;
+                    tokens.extend(quote!(#name #args));
+                } else {
+                    tokens.extend(quote!(#name));
+                }
+            }
+            if input.is_empty() {
+                break;
+            }
+            let comma: Token![,] = input.parse()?;
+            tokens.extend(quote!(#comma));
+        }
+        Ok(Self {
+            args: syn::parse2(tokens)?,
+            quote,
+        })
+    }
+}
+
+#[derive(StructMeta)]
+struct KeyValueArgs {
+    sep: Option<LitStr>,
+    assign: Option<LitStr>,
+}
+
+fn check_sep_not_empty(sep: &LitStr) -> Result<()> {
+    if sep.value().is_empty() {
+        bail!(sep.span(), "`sep` cannot be empty.");
+    }
+    Ok(())
+}
+
+#[derive(Clone)]
+struct KeyValueFormat {
+    sep: String,
+    assign: String,
+    span: Span,
 }
 
 #[derive(Clone, ToTokens)]
@@ -743,26 +2146,106 @@ impl Parse for DefaultField {
 struct FromStrArgs {
     regex: Option<LitStr>,
     new: Option<Expr>,
+    validate: Option<Path>,
     bound: Option<Vec<Quotable<Bound>>>,
+    any_order: Option<NameArgs<Option<AnyOrderArgs>>>,
+    infer_regex: Option<LitBool>,
+    whitespace: Option<LitStr>,
+    trim: Flag,
+    greedy: Flag,
+    priority: Option<LitInt>,
+    #[struct_meta(name = "match")]
+    match_policy: Option<LitStr>,
     default: Flag,
     default_fields: Option<Vec<Quotable<DefaultField>>>,
     ignore: Flag,
-    dump: bool,
+    dump: Option<NameArgs<Option<Ident>>>,
+}
+
+#[derive(StructMeta)]
+struct AnyOrderArgs {
+    sep: LitStr,
+}
+
+struct FromStrArgsWithDefault {
+    args: FromStrArgs,
+    default_expr: Option<Expr>,
+}
+impl Parse for FromStrArgsWithDefault {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut tokens = TokenStream::new();
+        let mut default_expr = None;
+        while !input.is_empty() {
+            let name = Ident::parse_any(input)?;
+            if input.peek(Token![=]) && input.peek2(Token![@]) && name == "regex" {
+                let eq: Token![=] = input.parse()?;
+                input.parse::<Token![@]>()?;
+                let preset = Ident::parse_any(input)?;
+                let value = match regex_preset(&preset.to_string()) {
+                    Some(value) => LitStr::new(value, preset.span()),
+                    None => bail!(
+                        preset.span(),
+                        "{}",
+                        unknown_regex_preset_message(&preset.to_string())
+                    ),
+                };
+                tokens.extend(quote!(#name #eq #value));
+            } else if input.peek(Token![=]) {
+                let eq: Token![=] = input.parse()?;
+                let value: Expr = input.parse()?;
+                if name == "default" {
+                    if default_expr.is_some() {
+                        bail!(name.span(), "parameter `default` specified more than once");
+                    }
+                    default_expr = Some(value);
+                } else {
+                    tokens.extend(quote!(#name #eq #value));
+                }
+            } else if input.peek(syn::token::Paren) {
+                let args: proc_macro2::TokenTree = input.parse()?;
+                tokens.extend(quote!(#name #args));
+            } else {
+                tokens.extend(quote!(#name));
+            }
+            if input.is_empty() {
+                break;
+            }
+            let comma: Token![,] = input.parse()?;
+            tokens.extend(quote!(#comma));
+        }
+        Ok(Self {
+            args: syn::parse2(tokens)?,
+            default_expr,
+        })
+    }
 }
 
 #[derive(Clone)]
 struct HelperAttributes {
     format: Option<DisplayFormat>,
-    style: Option<DisplayStyle>,
+    style: Option<DisplayCase>,
+    digits: Option<DigitSplit>,
     bound_display: Option<Vec<Bound>>,
     bound_from_str: Option<Vec<Bound>>,
     regex: Option<LitStr>,
     default_self: Option<Span>,
+    default_expr: Option<Expr>,
     default_fields: Vec<DefaultField>,
     new_expr: Option<Expr>,
+    validate: Option<Path>,
+    any_order: Option<LitStr>,
+    infer_regex: Option<bool>,
+    whitespace: Option<(Whitespace, Span)>,
+    trim: bool,
+    greedy: bool,
+    priority: Option<(i32, Span)>,
+    match_policy: Option<(MatchPolicy, Span)>,
+    quote: Option<char>,
     ignore: Flag,
-    dump_display: bool,
-    dump_from_str: bool,
+    discriminant: Option<Span>,
+    kv: Option<KeyValueFormat>,
+    dump_display: Option<Dump>,
+    dump_from_str: Option<Dump>,
     crate_path: Path,
 }
 impl HelperAttributes {
@@ -770,23 +2253,45 @@ impl HelperAttributes {
         let mut hattrs = Self {
             format: None,
             style: None,
+            digits: None,
             bound_display: None,
             bound_from_str: None,
             regex: None,
             new_expr: None,
+            validate: None,
+            any_order: None,
+            infer_regex: None,
+            whitespace: None,
+            trim: false,
+            greedy: false,
+            priority: None,
+            match_policy: None,
+            quote: None,
             default_self: None,
+            default_expr: None,
             default_fields: Vec::new(),
             ignore: Flag::NONE,
-            dump_display: false,
-            dump_from_str: false,
+            discriminant: None,
+            kv: None,
+            dump_display: None,
+            dump_from_str: None,
             crate_path: parse_quote!(::parse_display),
         };
         for a in attrs {
             if a.path().is_ident("display") {
-                hattrs.set_display_args(a.parse_args()?)?;
+                let args: DisplayArgsWithQuote = a.parse_args()?;
+                if let Some(quote) = args.quote {
+                    hattrs.quote = Some(parse_quote_char(&quote)?);
+                }
+                hattrs.set_display_args(args.args)?;
             }
             if a.path().is_ident("from_str") {
-                hattrs.set_from_str_args(a.parse_args()?);
+                let args: FromStrArgsWithDefault = a.parse_args()?;
+                if let Some(expr) = args.default_expr {
+                    hattrs.default_self = Some(expr.span());
+                    hattrs.default_expr = Some(expr);
+                }
+                hattrs.set_from_str_args(args.args)?;
             }
         }
         Ok(hattrs)
@@ -795,8 +2300,27 @@ impl HelperAttributes {
         if let Some(format) = &args.format {
             self.format = Some(DisplayFormat::parse_lit_str(format)?);
         }
+        if !args.args.is_empty() {
+            let mut format_args: Vec<_> = args.args.into_iter().collect();
+            format_args.sort_by(|a, b| a.0.cmp(&b.0));
+            for (name, arg) in &format_args {
+                if !self.format.as_ref().is_some_and(|f| f.is_arg_used(name)) {
+                    bail!(
+                        arg.name_span,
+                        "cannot find parameter `{name}` in this scope. (format arguments must be used in `#[display(\"format\")]`)"
+                    );
+                }
+            }
+            self.format.as_mut().unwrap().args = format_args
+                .into_iter()
+                .map(|(name, arg)| (name, arg.value))
+                .collect();
+        }
         if let Some(style) = &args.style {
-            self.style = Some(DisplayStyle::parse_lit_str(style)?);
+            self.style = Some(DisplayCase::parse_lit_str(style)?);
+        }
+        if let Some(digits) = &args.digits {
+            self.digits = Some(DigitSplit::parse_lit_str(digits)?);
         }
         if let Some(bounds) = args.bound {
             let list = self.bound_display.get_or_insert(Vec::new());
@@ -809,17 +2333,68 @@ impl HelperAttributes {
         if let Some(crate_path) = &args.crate_path {
             self.crate_path = crate_path.clone();
         }
-        self.dump_from_str |= args.dump;
-        self.dump_display |= args.dump;
+        if let Some(span) = args.discriminant.span {
+            self.discriminant = Some(span);
+        }
+        if let Some(kv) = &args.kv {
+            let args = kv.args.as_ref();
+            if let Some(sep) = args.and_then(|a| a.sep.as_ref()) {
+                check_sep_not_empty(sep)?;
+            }
+            let value = |lit: Option<&LitStr>, default: &str| {
+                lit.map_or_else(|| default.to_string(), |lit| lit.value())
+            };
+            self.kv = Some(KeyValueFormat {
+                sep: value(args.and_then(|a| a.sep.as_ref()), " "),
+                assign: value(args.and_then(|a| a.assign.as_ref()), "="),
+                span: kv.name_span,
+            });
+        }
+        if let Some(dump) = &args.dump {
+            let dump = Dump::from_args(dump)?;
+            self.dump_from_str = Some(dump);
+            self.dump_display = Some(dump);
+        }
+        if args.quote.value() {
+            self.quote = Some('"');
+        }
         Ok(())
     }
-    fn set_from_str_args(&mut self, args: FromStrArgs) {
+    fn set_from_str_args(&mut self, args: FromStrArgs) -> Result<()> {
         if let Some(regex) = args.regex {
             self.regex = Some(regex);
         }
         if let Some(new) = args.new {
             self.new_expr = Some(new);
         }
+        if let Some(validate) = args.validate {
+            self.validate = Some(validate);
+        }
+        if let Some(any_order) = args.any_order {
+            let span = any_order.name_span;
+            let sep = any_order
+                .args
+                .map_or_else(|| LitStr::new(" ", span), |args| args.sep);
+            check_sep_not_empty(&sep)?;
+            self.any_order = Some(sep);
+        }
+        if let Some(infer_regex) = args.infer_regex {
+            self.infer_regex = Some(infer_regex.value);
+        }
+        if let Some(whitespace) = &args.whitespace {
+            self.whitespace = Some((Whitespace::parse_lit_str(whitespace)?, whitespace.span()));
+        }
+        self.trim |= args.trim.value();
+        self.greedy |= args.greedy.value();
+        if let Some(priority) = &args.priority {
+            self.priority = Some((priority.base10_parse()?, priority.span()));
+        }
+        if let Some(match_policy) = &args.match_policy {
+            self.match_policy = Some((
+                MatchPolicy::parse_lit_str(match_policy)?,
+                match_policy.span(),
+            ));
+        }
         if let Some(bound) = args.bound {
             let list = self.bound_from_str.get_or_insert(Vec::new());
             for bound in bound {
@@ -841,7 +2416,19 @@ impl HelperAttributes {
         if args.ignore.value() {
             self.ignore = args.ignore;
         }
-        self.dump_from_str |= args.dump;
+        if let Some(dump) = &args.dump {
+            self.dump_from_str = Some(Dump::from_args(dump)?);
+        }
+        Ok(())
+    }
+    fn check_kv_not_specified(&self) -> Result<()> {
+        if let Some(kv) = &self.kv {
+            bail!(
+                kv.span,
+                "`#[display(kv)]` can be specified only for struct."
+            );
+        }
+        Ok(())
     }
     fn span_of_from_str_format(&self) -> Option<Span> {
         if let Some(lit) = &self.regex {
@@ -860,7 +2447,7 @@ impl HelperAttributes {
 }
 
 #[derive(Copy, Clone)]
-enum DisplayStyle {
+enum DisplayCase {
     None,
     LowerCase,
     UpperCase,
@@ -874,9 +2461,13 @@ enum DisplayStyle {
     TitleCaseHead,
     TitleCaseLower,
     TitleCaseUpper,
+    DotCase,
+    TrainCase,
+    PathCase,
+    AdaCase,
 }
 
-impl DisplayStyle {
+impl DisplayCase {
     fn parse_lit_str(s: &LitStr) -> Result<Self> {
         const ERROR_MESSAGE: &str = "Invalid display style. \
         The following values are available: \
@@ -889,17 +2480,22 @@ impl DisplayStyle {
         \"CamelCase\", \
         \"kebab-case\", \
         \"KEBAB-CASE\", \
+        \"COBOL-CASE\", \
         \"Title Case\", \
         \"Title case\", \
         \"title case\", \
-        \"TITLE CASE\"";
+        \"TITLE CASE\", \
+        \"dot.case\", \
+        \"Train-Case\", \
+        \"path/case\", \
+        \"Ada_Case\"";
         match Self::parse(&s.value()) {
             Err(_) => bail!(s.span(), "{ERROR_MESSAGE}"),
             Ok(value) => Ok(value),
         }
     }
     fn parse(s: &str) -> std::result::Result<Self, ParseDisplayStyleError> {
-        use DisplayStyle::*;
+        use DisplayCase::*;
         Ok(match s {
             "none" => None,
             "lowercase" => LowerCase,
@@ -909,79 +2505,233 @@ impl DisplayStyle {
             "camelCase" => LowerCamelCase,
             "CamelCase" => UpperCamelCase,
             "kebab-case" => LowerKebabCase,
-            "KEBAB-CASE" => UpperKebabCase,
+            "KEBAB-CASE" | "COBOL-CASE" => UpperKebabCase,
             "Title Case" => TitleCase,
             "Title case" => TitleCaseHead,
             "title case" => TitleCaseLower,
             "TITLE CASE" => TitleCaseUpper,
+            "dot.case" => DotCase,
+            "Train-Case" => TrainCase,
+            "path/case" => PathCase,
+            "Ada_Case" => AdaCase,
             _ => return Err(ParseDisplayStyleError),
         })
     }
+}
+
+/// How digits split words in [`DisplayStyle::apply`].
+#[derive(Copy, Clone)]
+enum DigitSplit {
+    /// Digits are the end of the preceding word. (`Var2D` -> `var2_d`)
+    Suffix,
+    /// Digits are the start of a new word. (`Var2D` -> `var_2d`)
+    Prefix,
+    /// Digits are a word of their own. (`Var2D` -> `var_2_d`)
+    Separate,
+}
+
+impl DigitSplit {
+    fn parse_lit_str(s: &LitStr) -> Result<Self> {
+        Ok(match s.value().as_str() {
+            "suffix" => Self::Suffix,
+            "prefix" => Self::Prefix,
+            "separate" => Self::Separate,
+            _ => bail!(
+                s.span(),
+                "Invalid digits. \
+                The following values are available: \
+                \"suffix\", \
+                \"prefix\", \
+                \"separate\""
+            ),
+        })
+    }
+}
+
+fn parse_quote_char(s: &LitStr) -> Result<char> {
+    let value = s.value();
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c != '\\' => Ok(c),
+        _ => bail!(
+            s.span(),
+            "quote must be a single character other than `\\`."
+        ),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Whitespace {
+    Exact,
+    Flexible,
+}
+impl Whitespace {
+    fn parse_lit_str(s: &LitStr) -> Result<Self> {
+        Ok(match s.value().as_str() {
+            "exact" => Self::Exact,
+            "flexible" => Self::Flexible,
+            _ => bail!(
+                s.span(),
+                "Invalid whitespace. \
+                The following values are available: \
+                \"exact\", \
+                \"flexible\""
+            ),
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum MatchPolicy {
+    First,
+    Longest,
+}
+impl MatchPolicy {
+    fn parse_lit_str(s: &LitStr) -> Result<Self> {
+        Ok(match s.value().as_str() {
+            "first" => Self::First,
+            "longest" => Self::Longest,
+            _ => bail!(
+                s.span(),
+                "Invalid match policy. \
+                The following values are available: \
+                \"first\", \
+                \"longest\""
+            ),
+        })
+    }
+}
+
+#[derive(Copy, Clone)]
+struct DisplayStyle {
+    case: DisplayCase,
+    digits: DigitSplit,
+}
+
+impl DisplayStyle {
     fn from_helper_attributes(
         hattrs_enum: &HelperAttributes,
         hattrs_variant: &HelperAttributes,
     ) -> Self {
-        hattrs_variant
-            .style
-            .or(hattrs_enum.style)
-            .unwrap_or(DisplayStyle::None)
+        Self {
+            case: hattrs_variant
+                .style
+                .or(hattrs_enum.style)
+                .unwrap_or(DisplayCase::None),
+            digits: hattrs_variant
+                .digits
+                .or(hattrs_enum.digits)
+                .unwrap_or(DigitSplit::Suffix),
+        }
     }
+    fn from_helper_attributes_struct(hattrs: &HelperAttributes) -> Self {
+        Self::from_helper_attributes(hattrs, hattrs)
+    }
+
+    /// Convert `ident` to this style.
+    ///
+    /// `ident` is split into words by the following rules, and the words are joined by the separator of the style.
+    ///
+    /// - Characters other than letters and digits separate words and are removed. (`_Abc_Def` -> `Abc`, `Def`)
+    /// - An uppercase letter following a lowercase letter starts a word. (`AbcDef` -> `Abc`, `Def`)
+    /// - In a run of uppercase letters, the last one starts a word if a lowercase letter follows it. (`HTTPServer` -> `HTTP`, `Server`)
+    /// - Digits split words according to [`DigitSplit`].
+    ///
+    /// Letters are classified and converted with the Unicode case mapping. (`ÉtatInitial` -> `état_initial`)
     fn apply(self, ident: &Ident) -> String {
-        fn convert_case(c: char, to_upper: bool) -> char {
+        fn push_case(r: &mut String, c: char, to_upper: bool) {
             if to_upper {
-                c.to_ascii_uppercase()
+                r.extend(c.to_uppercase());
             } else {
-                c.to_ascii_lowercase()
+                r.extend(c.to_lowercase());
             }
         }
 
-        let s = ident.to_string();
-        let (line_head, word_head, normal, sep) = match self {
-            DisplayStyle::None => {
+        let s = ident.unraw().to_string();
+        let (line_head, word_head, normal, sep) = match self.case {
+            DisplayCase::None => {
                 return s;
             }
-            DisplayStyle::LowerCase => (false, false, false, ""),
-            DisplayStyle::UpperCase => (true, true, true, ""),
-            DisplayStyle::LowerSnakeCase => (false, false, false, "_"),
-            DisplayStyle::UpperSnakeCase => (true, true, true, "_"),
-            DisplayStyle::LowerCamelCase => (false, true, false, ""),
-            DisplayStyle::UpperCamelCase => (true, true, false, ""),
-            DisplayStyle::LowerKebabCase => (false, false, false, "-"),
-            DisplayStyle::UpperKebabCase => (true, true, true, "-"),
-            DisplayStyle::TitleCase => (true, true, false, " "),
-            DisplayStyle::TitleCaseUpper => (true, true, true, " "),
-            DisplayStyle::TitleCaseLower => (false, false, false, " "),
-            DisplayStyle::TitleCaseHead => (true, false, false, " "),
+            DisplayCase::LowerCase => (false, false, false, ""),
+            DisplayCase::UpperCase => (true, true, true, ""),
+            DisplayCase::LowerSnakeCase => (false, false, false, "_"),
+            DisplayCase::UpperSnakeCase => (true, true, true, "_"),
+            DisplayCase::LowerCamelCase => (false, true, false, ""),
+            DisplayCase::UpperCamelCase => (true, true, false, ""),
+            DisplayCase::LowerKebabCase => (false, false, false, "-"),
+            DisplayCase::UpperKebabCase => (true, true, true, "-"),
+            DisplayCase::TitleCase => (true, true, false, " "),
+            DisplayCase::TitleCaseUpper => (true, true, true, " "),
+            DisplayCase::TitleCaseLower => (false, false, false, " "),
+            DisplayCase::TitleCaseHead => (true, false, false, " "),
+            DisplayCase::DotCase => (false, false, false, "."),
+            DisplayCase::TrainCase => (true, true, false, "-"),
+            DisplayCase::PathCase => (false, false, false, "/"),
+            DisplayCase::AdaCase => (true, true, false, "_"),
         };
-        let mut is_line_head = true;
-        let mut is_word_head = true;
-        let mut last = '\0';
-
         let mut r = String::new();
-        for c in s.chars() {
-            if !c.is_alphanumeric() && !c.is_ascii_digit() {
-                is_word_head = true;
-                continue;
+        for (i, word) in self.split_words(&s).into_iter().enumerate() {
+            if i != 0 {
+                r.push_str(sep);
+            }
+            for (j, c) in word.chars().enumerate() {
+                let to_upper = match (i, j) {
+                    (0, 0) => line_head,
+                    (_, 0) => word_head,
+                    _ => normal,
+                };
+                push_case(&mut r, c, to_upper);
             }
-            is_word_head = is_word_head || (!last.is_ascii_uppercase() && c.is_ascii_uppercase());
-            last = c;
-            let (to_upper, sep) = match (is_line_head, is_word_head) {
-                (true, _) => (line_head, ""),
-                (false, true) => (word_head, sep),
-                (false, false) => (normal, ""),
-            };
-            r.push_str(sep);
-            r.push(convert_case(c, to_upper));
-            is_word_head = false;
-            is_line_head = false;
         }
         r
     }
+    fn split_words(self, s: &str) -> Vec<String> {
+        let cs: Vec<char> = s.chars().collect();
+        let mut words = Vec::new();
+        let mut word = String::new();
+        for (i, &c) in cs.iter().enumerate() {
+            if !c.is_alphanumeric() {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+                continue;
+            }
+            if !word.is_empty() {
+                let next = cs.get(i + 1).copied().unwrap_or('\0');
+                if self.is_word_boundary(cs[i - 1], c, next) {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            word.push(c);
+        }
+        if !word.is_empty() {
+            words.push(word);
+        }
+        words
+    }
+    fn is_word_boundary(self, last: char, c: char, next: char) -> bool {
+        let is_digit = |c: char| c.is_numeric();
+        let is_upper = |c: char| c.is_uppercase();
+        let is_lower = |c: char| c.is_alphabetic() && !is_upper(c);
+        match (is_digit(last), is_digit(c)) {
+            (false, true) => !matches!(self.digits, DigitSplit::Suffix),
+            (true, false) => match self.digits {
+                DigitSplit::Suffix => is_upper(c),
+                DigitSplit::Prefix => false,
+                DigitSplit::Separate => true,
+            },
+            (true, true) => false,
+            (false, false) => is_upper(c) && (is_lower(last) || is_upper(last) && is_lower(next)),
+        }
+    }
 }
 
+static REGEX_SUB_ARG: Lazy<Regex> = lazy_regex!(r"(^|[^a-zA-Z0-9_])([a-zA-Z0-9_]+)\$");
+
 #[derive(Clone)]
 struct DisplayFormat {
     parts: Vec<DisplayFormatPart>,
+    args: Vec<(String, Expr)>,
     span: Span,
 }
 impl DisplayFormat {
@@ -990,7 +2740,9 @@ impl DisplayFormat {
     }
     fn parse(mut s: &str, span: Span) -> Result<DisplayFormat> {
         static REGEX_STR: Lazy<Regex> = lazy_regex!(r"^[^{}]+");
-        static REGEX_VAR: Lazy<Regex> = lazy_regex!(r"^\{([^:{}]*)(?::([^}]*))?\}");
+        static REGEX_VAR: Lazy<Regex> = lazy_regex!(r"^\{((?:[^:{}]|::)*)(?::([^}]*))?\}");
+        static REGEX_COND: Lazy<Regex> = lazy_regex!(r"^\{\?([^:{}]*):([^{}]*)\}");
+        static REGEX_FIELD_NAME: Lazy<Regex> = lazy_regex!(r"^\{#([^:{}]*)\}");
         let mut parts = Vec::new();
         while !s.is_empty() {
             if s.starts_with("{{") {
@@ -1008,6 +2760,22 @@ impl DisplayFormat {
                 s = &s[m.end()..];
                 continue;
             }
+            if let Some(c) = REGEX_COND.captures(s) {
+                if c[2].is_empty() {
+                    bail!(span, "the text of `{{?{}:}}` cannot be empty.", &c[1]);
+                }
+                let arg = c[1].into();
+                let text = c[2].into();
+                parts.push(DisplayFormatPart::Cond { arg, text });
+                s = &s[c.get(0).unwrap().end()..];
+                continue;
+            }
+            if let Some(c) = REGEX_FIELD_NAME.captures(s) {
+                let arg = c[1].into();
+                parts.push(DisplayFormatPart::FieldName { arg });
+                s = &s[c.get(0).unwrap().end()..];
+                continue;
+            }
             if let Some(c) = REGEX_VAR.captures(s) {
                 let arg = c.get(1).unwrap().as_str().into();
                 let format_spec = c.get(2).map_or("", |x| x.as_str()).into();
@@ -1017,7 +2785,11 @@ impl DisplayFormat {
             }
             bail!(span, "invalid display format.");
         }
-        Ok(Self { parts, span })
+        Ok(Self {
+            parts,
+            args: Vec::new(),
+            span,
+        })
     }
     fn from_newtype_struct(data: &DataStruct) -> Option<Self> {
         let p = DisplayFormatPart::Var {
@@ -1026,12 +2798,33 @@ impl DisplayFormat {
         };
         Some(Self {
             parts: vec![p],
+            args: Vec::new(),
             span: data.fields.span(),
         })
     }
-    fn from_unit_variant(variant: &Variant) -> Result<Option<Self>> {
+    fn from_kv(fields: &Fields, kv: &KeyValueFormat) -> Result<Self> {
+        fn escape(s: &str) -> String {
+            s.replace('{', "{{").replace('}', "}}")
+        }
+        let assign = escape(&kv.assign);
+        let sep = escape(&kv.sep);
+        let mut format = String::new();
+        for (index, field) in fields.iter().enumerate() {
+            let key = match &field.ident {
+                Some(ident) => FieldKey::from_ident(ident),
+                None => FieldKey::Unnamed(index),
+            };
+            if index != 0 {
+                format.push_str(&sep);
+            }
+            format.push_str(&format!("{{#{key}}}{assign}{{{key}}}"));
+        }
+        Self::parse(&format, kv.span)
+    }
+    fn from_unit_variant(variant: &Variant, discriminant: bool) -> Result<Option<Self>> {
         Ok(if let Fields::Unit = &variant.fields {
-            Some(Self::parse("{}", variant.span())?)
+            let format = if discriminant { "{discriminant}" } else { "{}" };
+            Some(Self::parse(format, variant.span())?)
         } else {
             None
         })
@@ -1045,27 +2838,92 @@ impl DisplayFormat {
     ) -> Result<TokenStream> {
         let mut format_str = String::new();
         let mut format_args = Vec::new();
+        let mut sub_args = Vec::new();
         for p in &self.parts {
             use DisplayFormatPart::*;
             match p {
                 Str(s) => format_str.push_str(s.as_str()),
                 EscapedBeginBracket => format_str.push_str("{{"),
                 EscapedEndBracket => format_str.push_str("}}"),
+                Cond { arg, text } => {
+                    format_str.push_str("{}");
+                    let cond = context.cond_arg(arg, self.span)?;
+                    format_args.push(quote!(if #cond { #text } else { "" }));
+                }
+                FieldName { arg } => {
+                    format_str.push_str(&context.field_name(arg, self.span)?);
+                }
                 Var { arg, format_spec } => {
                     format_str.push('{');
                     if !format_spec.is_empty() {
                         format_str.push(':');
-                        format_str.push_str(format_spec);
+                        format_str.push_str(&self.bind_sub_args(
+                            format_spec,
+                            &context,
+                            &mut sub_args,
+                        )?);
                     }
                     format_str.push('}');
-                    let format_arg =
-                        context.format_arg(arg, format_spec, self.span, bounds, generics)?;
+                    let format_arg = if let Some(expr) = self.arg_expr(arg)? {
+                        quote!(#expr)
+                    } else {
+                        context.format_arg(arg, format_spec, self.span, bounds, generics)?
+                    };
                     format_args.push(quote!(&#format_arg));
                 }
             }
         }
         let format_str = LitStr::new(&format_str, self.span);
-        Ok(quote! { #format_str #(,#format_args)* })
+        let sub_args = sub_args.iter().map(|(name, expr)| quote!(#name = #expr));
+        Ok(quote! { #format_str #(,#format_args)* #(,#sub_args)* })
+    }
+    fn bind_sub_args(
+        &self,
+        format_spec: &str,
+        context: &DisplayContext,
+        sub_args: &mut Vec<(Ident, TokenStream)>,
+    ) -> Result<String> {
+        try_replace_all(
+            &REGEX_SUB_ARG,
+            format_spec,
+            |c: &Captures| -> Result<String> {
+                let name = &c[2];
+                let key = FieldKey::from_str(name);
+                let var = key.new_arg_var();
+                if !sub_args.iter().any(|(n, _)| n == &var) {
+                    let expr = if let Some(expr) = self.arg_expr(name)? {
+                        quote!(#expr)
+                    } else {
+                        context.sub_arg(&key, self.span)?
+                    };
+                    sub_args.push((var.clone(), expr));
+                }
+                Ok(format!("{}{}$", &c[1], var.unraw()))
+            },
+        )
+    }
+    fn is_arg_used(&self, name: &str) -> bool {
+        self.parts.iter().any(|p| match p {
+            DisplayFormatPart::Var { arg, format_spec } => {
+                arg == name
+                    || REGEX_SUB_ARG
+                        .captures_iter(format_spec)
+                        .any(|c| &c[2] == name)
+            }
+            _ => false,
+        })
+    }
+    fn arg_expr(&self, arg: &str) -> Result<Option<Expr>> {
+        if let Some((_, expr)) = self.args.iter().find(|(name, _)| name == arg) {
+            return Ok(Some(expr.clone()));
+        }
+        if arg.contains("::") {
+            return match parse_str(arg) {
+                Ok(expr) => Ok(Some(expr)),
+                Err(_) => bail!(self.span, "invalid path `{}`.", arg),
+            };
+        }
+        Ok(None)
     }
 }
 
@@ -1075,15 +2933,21 @@ enum DisplayFormatPart {
     EscapedBeginBracket,
     EscapedEndBracket,
     Var { arg: String, format_spec: String },
+    Cond { arg: String, text: String },
+    FieldName { arg: String },
 }
 
 enum DisplayContext<'a> {
     Struct {
         data: &'a DataStruct,
+        style: DisplayStyle,
+        crate_path: &'a Path,
     },
     Variant {
         variant: &'a Variant,
         style: DisplayStyle,
+        discriminant: Option<&'a Discriminant>,
+        crate_path: &'a Path,
     },
     Field {
         parent: &'a DisplayContext<'a>,
@@ -1101,6 +2965,16 @@ impl<'a> DisplayContext<'a> {
         bounds: &mut Bounds,
         generics: &GenericParamSet,
     ) -> Result<TokenStream> {
+        if let DisplayContext::Variant {
+            variant,
+            discriminant: Some(discriminant),
+            ..
+        } = self
+        {
+            if arg == DISCRIMINANT_ARG {
+                return Ok(discriminant.to_expr(variant));
+            }
+        }
         let keys = FieldKey::from_str_deep(arg);
         if keys.is_empty() {
             return Ok(match self {
@@ -1150,6 +3024,7 @@ impl<'a> DisplayContext<'a> {
         generics: &GenericParamSet,
     ) -> Result<TokenStream> {
         let hattrs = HelperAttributes::from(&field.attrs)?;
+        hattrs.check_kv_not_specified()?;
         let mut bounds = bounds.child(hattrs.bound_display);
         Ok(if let Some(format) = hattrs.format {
             let args = format.format_args(
@@ -1176,6 +3051,11 @@ impl<'a> DisplayContext<'a> {
         generics: &GenericParamSet,
     ) -> Result<TokenStream> {
         let ty = &field.ty;
+        let mut expr = self.field_expr(key);
+        if let Some(quote) = HelperAttributes::from(&field.attrs)?.quote {
+            let crate_path = self.crate_path();
+            expr = quote! { #crate_path::QuotedDisplay::new(&#expr, #quote) };
+        }
         if generics.contains_in_type(ty) {
             let ps = match FormatSpec::parse(format_spec) {
                 Ok(ps) => ps,
@@ -1187,9 +3067,76 @@ impl<'a> DisplayContext<'a> {
                 bounds.pred.push(parse_quote!(#ty : ::core::fmt::#tr));
             }
         }
-        Ok(self.field_expr(key))
+        Ok(expr)
     }
 
+    fn cond_arg(&self, arg: &str, span: Span) -> Result<TokenStream> {
+        let keys = FieldKey::from_str_deep(arg);
+        let (context, key, sub_keys) = match self {
+            DisplayContext::Field { parent, key, .. } => (*parent, *key, &keys[..]),
+            _ => {
+                let key = match keys.first() {
+                    Some(key) => key,
+                    None => bail!(span, "field name is required in conditional format."),
+                };
+                if let Some(fields) = self.fields() {
+                    if !field_map(fields).contains_key(key) {
+                        bail!(span, "unknown field '{}'.", key);
+                    }
+                }
+                (self, key, &keys[1..])
+            }
+        };
+        let mut expr = context.field_expr(key);
+        if sub_keys.is_empty() {
+            if let DisplayContext::Variant { .. } = context {
+                return Ok(quote! { *#expr });
+            }
+        }
+        for key in sub_keys {
+            expr.extend(quote! { .#key });
+        }
+        Ok(expr)
+    }
+    fn sub_arg(&self, key: &FieldKey, span: Span) -> Result<TokenStream> {
+        let context = if let DisplayContext::Field { parent, .. } = self {
+            parent
+        } else {
+            self
+        };
+        if let Some(fields) = context.fields() {
+            if !field_map(fields).contains_key(key) {
+                bail!(span, "unknown field '{}'.", key);
+            }
+        }
+        let expr = context.field_expr(key);
+        Ok(if let DisplayContext::Variant { .. } = context {
+            quote! { *#expr }
+        } else {
+            expr
+        })
+    }
+    fn field_name(&self, arg: &str, span: Span) -> Result<String> {
+        let (fields, style) = match self {
+            DisplayContext::Struct { data, style, .. } => (&data.fields, style),
+            DisplayContext::Variant { variant, style, .. } => (&variant.fields, style),
+            DisplayContext::Field { .. } => {
+                bail!(span, "field name cannot be used in field format.")
+            }
+        };
+        let key = match &FieldKey::from_str_deep(arg)[..] {
+            [key] => key.clone(),
+            _ => bail!(span, "`{{#{}}}` must be a field name.", arg),
+        };
+        let field = match field_map(fields).get(&key) {
+            Some(field) => *field,
+            None => bail!(span, "unknown field '{}'.", key),
+        };
+        Ok(match &field.ident {
+            Some(ident) => style.apply(ident),
+            None => key.to_string(),
+        })
+    }
     fn field_expr(&self, key: &FieldKey) -> TokenStream {
         match self {
             DisplayContext::Struct { .. } => quote! { self.#key },
@@ -1213,14 +3160,35 @@ impl<'a> DisplayContext<'a> {
         const ERROR_MESSAGE_FOR_VARIANT:&str="`#[display(\"format\")]` or `#[from_str(regex = \"regex\")]` is required except unit variant.";
         Ok(match self {
             DisplayContext::Struct { data, .. } => {
-                DisplayFormat::from_newtype_struct(data).expect(ERROR_MESSAGE_FOR_STRUCT)
+                if let Some(format) = DisplayFormat::from_newtype_struct(data) {
+                    format
+                } else {
+                    bail!(data.struct_token.span, "{ERROR_MESSAGE_FOR_STRUCT}")
+                }
             }
-            DisplayContext::Variant { variant, .. } => {
-                DisplayFormat::from_unit_variant(variant)?.expect(ERROR_MESSAGE_FOR_VARIANT)
+            DisplayContext::Variant {
+                variant,
+                discriminant,
+                ..
+            } => {
+                if let Some(format) =
+                    DisplayFormat::from_unit_variant(variant, discriminant.is_some())?
+                {
+                    format
+                } else {
+                    bail!(variant.span(), "{ERROR_MESSAGE_FOR_VARIANT}")
+                }
             }
             DisplayContext::Field { field, .. } => DisplayFormat::parse("{}", field.span())?,
         })
     }
+    fn crate_path(&self) -> &Path {
+        match self {
+            DisplayContext::Struct { crate_path, .. } => crate_path,
+            DisplayContext::Variant { crate_path, .. } => crate_path,
+            DisplayContext::Field { parent, .. } => parent.crate_path(),
+        }
+    }
     fn fields(&self) -> Option<&Fields> {
         match self {
             DisplayContext::Struct { data, .. } => Some(&data.fields),
@@ -1254,7 +3222,7 @@ impl ParseFormat {
             Self::String(s) => {
                 let mut hirs = vec![Hir::look(regex_syntax::hir::Look::Start)];
                 push_str(&mut hirs, s);
-                std::mem::swap(self, &mut Self::Hirs(hirs));
+                *self = Self::Hirs(hirs);
             }
         }
         if let Self::Hirs(hirs) = self {
@@ -1272,6 +3240,86 @@ impl ParseFormat {
     fn push_hir(&mut self, hir: Hir) {
         self.as_hirs().push(hir);
     }
+    /// Returns the number of characters that are matched by text outside fields.
+    fn literal_len(&self) -> usize {
+        match self {
+            Self::Hirs(hirs) => hirs.iter().map(literal_len).sum(),
+            Self::String(s) => s.chars().count(),
+        }
+    }
+    fn take_segment(&mut self) -> Option<Hir> {
+        let hirs = self.as_hirs();
+        if let Some(HirKind::Look(Look::Start)) = hirs.first().map(|hir| hir.kind()) {
+            hirs.remove(0);
+        }
+        if hirs.is_empty() {
+            None
+        } else {
+            Some(Hir::concat(std::mem::take(hirs)))
+        }
+    }
+
+    /// Matches `segments` separated by `sep` in any order. (`^(?:(?:^|sep)(?:segment|...))*`)
+    ///
+    /// A separator at the start is not rejected by this regex, and is checked by the generated code.
+    fn push_any_order(&mut self, segments: Vec<Hir>, sep: &str) {
+        let start = Hir::alternation(vec![Hir::look(Look::Start), Hir::literal(sep.as_bytes())]);
+        let item = Hir::concat(vec![start, Hir::alternation(segments)]);
+        let hirs = self.as_hirs();
+        hirs.clear();
+        hirs.push(Hir::look(Look::Start));
+        hirs.push(Hir::repetition(Repetition {
+            min: 0,
+            max: None,
+            greedy: true,
+            sub: Box::new(item),
+        }));
+    }
+}
+
+const DISCRIMINANT_ARG: &str = "discriminant";
+
+struct Discriminant {
+    ty: Type,
+    value: Option<i128>,
+}
+impl Discriminant {
+    fn from_enum(
+        input: &DeriveInput,
+        data: &DataEnum,
+        hattrs: &HelperAttributes,
+    ) -> Result<Option<Vec<Self>>> {
+        if hattrs.discriminant.is_none() {
+            return Ok(None);
+        }
+        let ty = repr_int_type(&input.attrs)?.unwrap_or_else(|| parse_quote!(isize));
+        let mut ds = Vec::new();
+        let mut next = Some(0);
+        for variant in &data.variants {
+            if !matches!(variant.fields, Fields::Unit) {
+                bail!(
+                    variant.span(),
+                    "`#[display(discriminant)]` can be specified only for fieldless enum."
+                );
+            }
+            let value = if let Some((_, expr)) = &variant.discriminant {
+                eval_int_expr(expr)
+            } else {
+                next
+            };
+            next = value.and_then(|value| value.checked_add(1));
+            ds.push(Self {
+                ty: ty.clone(),
+                value,
+            });
+        }
+        Ok(Some(ds))
+    }
+    fn to_expr(&self, variant: &Variant) -> TokenStream {
+        let ident = &variant.ident;
+        let ty = &self.ty;
+        quote! { (Self::#ident as #ty) }
+    }
 }
 
 enum ParseVariantCode {
@@ -1339,7 +3387,7 @@ impl Bounds {
             Bound::Default(_) => self.can_extend = true,
         }
     }
-    fn child(&mut self, bounds: Option<Vec<Bound>>) -> BoundsChild {
+    fn child(&mut self, bounds: Option<Vec<Bound>>) -> BoundsChild<'_> {
         let bounds = if self.can_extend {
             Self::from_data(bounds)
         } else {
@@ -1448,10 +3496,9 @@ impl FieldKey {
     }
     fn new_arg_var(&self) -> Ident {
         match self {
-            Self::Named(s) => parse_str(s),
-            Self::Unnamed(idx) => parse_str(&format!("_{idx}")),
+            Self::Named(s) => format_ident!("r#{}", s),
+            Self::Unnamed(idx) => format_ident!("_{}", idx),
         }
-        .unwrap()
     }
 }
 impl std::fmt::Display for FieldKey {
@@ -1519,16 +3566,166 @@ fn capture_index(idx: usize, names: &HashMap<&str, usize>) -> usize {
     names[capture_name(idx).as_str()]
 }
 
+const REGEX_INT: &str = "[+-]?[0-9]+";
+const REGEX_UINT: &str = r"\+?[0-9]+";
+const REGEX_FLOAT: &str =
+    r"[+-]?(?:(?:[0-9]+(?:\.[0-9]*)?|\.[0-9]+)(?:[eE][+-]?[0-9]+)?|(?i:inf|infinity|nan))";
+
+const REGEX_PRESETS: &[(&str, &str)] = &[
+    ("int", REGEX_INT),
+    ("uint", REGEX_UINT),
+    ("float", REGEX_FLOAT),
+    ("ident", "[A-Za-z_][0-9A-Za-z_]*"),
+    ("word", r"\w+"),
+    ("hex", "[0-9a-fA-F]+"),
+    ("quoted", r#""(?:[^"\\]|\\.)*""#),
+    ("until_ws", r"\S+"),
+];
+
+fn regex_preset(name: &str) -> Option<&'static str> {
+    REGEX_PRESETS
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, regex)| *regex)
+}
+fn unknown_regex_preset_message(name: &str) -> String {
+    let names: Vec<_> = REGEX_PRESETS.iter().map(|(n, _)| format!("@{n}")).collect();
+    format!(
+        "unknown regex preset `@{name}`. (expected one of {})",
+        names.join(", ")
+    )
+}
+
+fn any_regex(greedy: bool, non_empty: bool) -> &'static str {
+    match (greedy, non_empty) {
+        (false, false) => "(?s:.*?)",
+        (true, false) => "(?s:.*)",
+        (false, true) => "(?s:.+?)",
+        (true, true) => "(?s:.+)",
+    }
+}
+
+fn type_regex(ty: &Type, parse_method: Option<ParseMethod>) -> Option<String> {
+    let number_type = NumberType::from_type(ty);
+    let is_signed = number_type == Some(NumberType::Signed);
+    let is_unsigned = number_type == Some(NumberType::Unsigned);
+    let is_float = number_type == Some(NumberType::Float);
+    let is_integer = is_signed || is_unsigned;
+    let ident = match ty {
+        Type::Path(ty) if ty.qself.is_none() => ty.path.get_ident()?.to_string(),
+        _ => return None,
+    };
+    Some(match parse_method {
+        Some(ParseMethod::Radix { radix, prefix }) if is_integer => {
+            let digits = match radix {
+                2 => "[01]",
+                8 => "[0-7]",
+                _ => "[0-9a-fA-F]",
+            };
+            format!(r"\+?{prefix}{digits}+")
+        }
+        Some(ParseMethod::Exp) if is_integer || is_float => REGEX_FLOAT.into(),
+        None if is_signed => REGEX_INT.into(),
+        None if is_unsigned => REGEX_UINT.into(),
+        None if is_float => REGEX_FLOAT.into(),
+        None if ident == "bool" => "true|false".into(),
+        None if ident == "char" => "(?s:.)".into(),
+        _ => return None,
+    })
+}
+
+/// Returns whether `ty` may be an enum that implements `FromStrRegex`.
+///
+/// Types with generic arguments are excluded, since the regex is stored in a static that cannot refer to them.
+fn may_be_unit_enum(ty: &Type, generics: &GenericParamSet) -> bool {
+    match ty {
+        Type::Path(path) => {
+            path.qself.is_none()
+                && path.path.segments.iter().all(|s| s.arguments.is_none())
+                && !generics.contains_in_type(ty)
+        }
+        _ => false,
+    }
+}
+
+/// Checks segments that are not checked by the regex, since it matches segments in any order.
+///
+/// Which segments are matched is determined by the captures of the segments in `re`.
+fn build_any_order_check(
+    crate_path: &Path,
+    re: &Regex,
+    sep: &str,
+    segments: &[AnyOrderSegment],
+    code: TokenStream,
+) -> TokenStream {
+    let mut indexes = Vec::new();
+    let mut duplicate_checks = Vec::new();
+    let mut missing_checks = Vec::new();
+    for segment in segments {
+        let name = Some(segment.capture.as_str());
+        let index = re.capture_names().position(|n| n == name).unwrap();
+        let key = &segment.key;
+        let msg = format!("`{key}` is specified more than once.");
+        duplicate_checks.push(quote_hygienic! {
+            if gap.starts_with(#key) {
+                return ::core::result::Result::Err(#crate_path::ParseError::with_message(#msg));
+            }
+        });
+        if segment.is_required {
+            let msg = format!("`{key}` is missing.");
+            missing_checks.push(quote_hygienic! {
+                if c.get(#index).is_none() {
+                    return ::core::result::Result::Err(#crate_path::ParseError::with_message(#msg));
+                }
+            });
+        }
+        indexes.push(index);
+    }
+    quote_hygienic! {
+        let gap = #crate_path::helpers::any_order_gap(
+            &s,
+            #sep,
+            &mut [#(c.get(#indexes).map(|m| (m.start(), m.end()))),*],
+        );
+        if let ::core::option::Option::Some(gap) = gap {
+            #(#duplicate_checks)*
+        } else {
+            #(#missing_checks)*
+            #code
+        }
+    }
+}
+
 fn build_parse_capture_expr(
     crate_path: &Path,
     field_name: &str,
     capture_index: usize,
+    parse_method: Option<ParseMethod>,
+    trim: bool,
+    quote: Option<char>,
 ) -> TokenStream {
     let msg = format!("field `{field_name}` parse failed.");
-    quote! {
-        c.get(#capture_index)
-            .map_or("", |m| m.as_str())
-            .parse()
-            .map_err(|e| #crate_path::ParseError::with_message(#msg))?
+    let mut s = quote_hygienic!(c.get(#capture_index).map_or("", |m| m.as_str()));
+    if trim {
+        s.extend(quote_hygienic!(.trim()));
+    }
+    if let Some(quote) = quote {
+        s = quote_hygienic! {
+            #crate_path::helpers::unquote(#s, #quote)
+                .ok_or_else(|| #crate_path::ParseError::with_message(#msg))?
+                .as_str()
+        };
+    }
+    let expr = match parse_method {
+        None => quote_hygienic!(#s.parse()),
+        Some(ParseMethod::Radix { radix, prefix }) => {
+            quote_hygienic!(#crate_path::helpers::FromStrRadix::from_str_radix(#s, #radix, #prefix))
+        }
+        Some(ParseMethod::Exp) => {
+            quote_hygienic!(#crate_path::helpers::FromStrExp::from_str_exp(#s))
+        }
+    };
+    quote_hygienic! {
+        #expr.map_err(|e| #crate_path::ParseError::with_message(#msg))?
     }
 }
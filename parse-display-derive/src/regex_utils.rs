@@ -1,6 +1,7 @@
 use regex::{Captures, Regex};
 use regex_syntax::ast::Ast;
-use regex_syntax::hir::Hir;
+use regex_syntax::hir::literal::Extractor;
+use regex_syntax::hir::{Hir, HirKind, Repetition};
 
 pub fn to_hir(s: &str) -> Hir {
     regex_syntax::Parser::new().parse(s).unwrap()
@@ -34,6 +35,67 @@ pub fn to_regex_string(hirs: &[Hir]) -> String {
     Hir::concat(hirs).to_string()
 }
 
+/// Removes captures and laziness, which do not affect the set of strings matched.
+pub fn normalize(hir: &Hir) -> Hir {
+    match hir.kind() {
+        HirKind::Capture(c) => normalize(&c.sub),
+        HirKind::Repetition(r) => Hir::repetition(Repetition {
+            min: r.min,
+            max: r.max,
+            greedy: true,
+            sub: Box::new(normalize(&r.sub)),
+        }),
+        HirKind::Concat(hirs) => Hir::concat(hirs.iter().map(normalize).collect()),
+        HirKind::Alternation(hirs) => Hir::alternation(hirs.iter().map(normalize).collect()),
+        _ => hir.clone(),
+    }
+}
+
+/// Returns the strings that `hir` can match, if they are finite and few.
+///
+/// Look-around assertions are ignored, so the result may contain strings that `hir` does not match.
+pub fn to_exact_strings(hir: &Hir) -> Option<Vec<String>> {
+    let seq = Extractor::new().extract(hir);
+    if !seq.is_exact() {
+        return None;
+    }
+    seq.literals()?
+        .iter()
+        .map(|lit| String::from_utf8(lit.as_bytes().to_vec()).ok())
+        .collect()
+}
+
+/// Returns the text that all strings matched by `hir` start with, up to the first non-literal.
+pub fn leading_literal(hir: &Hir) -> String {
+    let hirs = match hir.kind() {
+        HirKind::Concat(hirs) => hirs.as_slice(),
+        _ => std::slice::from_ref(hir),
+    };
+    let mut bytes = Vec::new();
+    for hir in hirs {
+        match hir.kind() {
+            HirKind::Literal(lit) => bytes.extend_from_slice(&lit.0),
+            _ => break,
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Returns the number of characters matched by literals outside named captures.
+pub fn literal_len(hir: &Hir) -> usize {
+    match hir.kind() {
+        HirKind::Literal(lit) => {
+            std::str::from_utf8(&lit.0).map_or(lit.0.len(), |s| s.chars().count())
+        }
+        HirKind::Capture(c) if c.name.is_some() => 0,
+        HirKind::Capture(c) => literal_len(&c.sub),
+        HirKind::Repetition(r) => literal_len(&r.sub) * r.min as usize,
+        HirKind::Concat(hirs) => hirs.iter().map(literal_len).sum(),
+        HirKind::Alternation(hirs) => hirs.iter().map(literal_len).min().unwrap_or(0),
+        _ => 0,
+    }
+}
+
 fn replace_ast(ast: &mut Ast, f: &mut impl FnMut(&mut Ast) -> bool) {
     if !f(ast) {
         return;
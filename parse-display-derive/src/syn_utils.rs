@@ -1,15 +1,18 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream, TokenTree};
 use quote::{quote, quote_spanned, ToTokens};
-use std::collections::HashSet;
+use std::{collections::HashSet, path::PathBuf};
+use structmeta::NameArgs;
 use syn::{
     ext::IdentExt,
     parse::discouraged::Speculative,
     parse::Parse,
     parse::ParseStream,
-    parse2, parse_str,
+    parse2, parse_quote, parse_str,
     punctuated::Punctuated,
+    token,
     visit::{visit_path, Visit},
-    DeriveInput, GenericParam, Generics, Ident, LitStr, Path, Result, Token, Type, WherePredicate,
+    Attribute, DeriveInput, Expr, ExprGroup, ExprLit, ExprParen, ExprUnary, GenericParam, Generics,
+    Ident, Lit, LitStr, Path, Result, Token, Type, UnOp, WherePredicate,
 };
 
 macro_rules! bail {
@@ -24,6 +27,14 @@ macro_rules! bail {
     };
 }
 
+/// Same as `quote!`, but local variables and lifetimes in the generated code are hygienic like `macro_rules!`,
+/// so they never conflict with identifiers in user code such as field names.
+macro_rules! quote_hygienic {
+    ($($tt:tt)*) => {
+        ::quote::quote_spanned!(::proc_macro2::Span::mixed_site()=> $($tt)*)
+    };
+}
+
 pub fn into_macro_output(input: Result<TokenStream>) -> proc_macro::TokenStream {
     match input {
         Ok(s) => s,
@@ -32,6 +43,19 @@ pub fn into_macro_output(input: Result<TokenStream>) -> proc_macro::TokenStream
     .into()
 }
 
+/// Emits a warning at `span` through the `deprecated` lint, since proc macros cannot emit warnings directly on stable.
+pub fn build_warning(span: Span, message: &str) -> TokenStream {
+    let ident = Ident::new("parse_display_warning", span);
+    quote_spanned! {span=>
+        {
+            #[deprecated(note = #message)]
+            #[allow(non_upper_case_globals)]
+            const #ident: () = ();
+            let _ = #ident;
+        }
+    }
+}
+
 pub struct GenericParamSet {
     idents: HashSet<Ident>,
 }
@@ -167,11 +191,119 @@ pub fn impl_trait_result(
     trait_path: &Path,
     wheres: &[WherePredicate],
     contents: TokenStream,
-    dump: bool,
+    dump: Option<Dump>,
+    dump_notes: &str,
 ) -> Result<TokenStream> {
-    let ts = impl_trait(input, trait_path, wheres, contents);
-    if dump {
-        panic!("macro output:\n{ts}");
+    let mut ts = impl_trait(input, trait_path, wheres, contents);
+    if let Some(dump) = dump {
+        let trait_name = &trait_path.segments.last().unwrap().ident;
+        let text = format!("macro output:\n{ts}\n{dump_notes}");
+        match dump.mode {
+            DumpMode::Panic => panic!("{text}"),
+            DumpMode::Warning => {
+                let warning = build_warning(
+                    dump.span,
+                    &format!(
+                        "dump of `#[derive({trait_name})]` for `{}`\n{text}",
+                        input.ident
+                    ),
+                );
+                ts.extend(quote!(const _: () = #warning;));
+            }
+            DumpMode::File => {
+                let out_dir = match std::env::var_os("OUT_DIR") {
+                    Some(out_dir) => PathBuf::from(out_dir),
+                    None => bail!(
+                        dump.span,
+                        "`dump(file)` requires `OUT_DIR`. Add a build script to the crate."
+                    ),
+                };
+                let dir = out_dir.join("parse-display-dump");
+                let path = dir.join(format!("{}.{trait_name}.txt", input.ident));
+                if let Err(e) =
+                    std::fs::create_dir_all(&dir).and_then(|_| std::fs::write(&path, &text))
+                {
+                    bail!(dump.span, "failed to write `{}`: {e}", path.display());
+                }
+            }
+        }
     }
     Ok(ts)
 }
+
+#[derive(Clone, Copy)]
+pub struct Dump {
+    pub mode: DumpMode,
+    pub span: Span,
+}
+impl Dump {
+    pub fn from_args(args: &NameArgs<Option<Ident>>) -> Result<Self> {
+        let mode = match &args.args {
+            None => DumpMode::Panic,
+            Some(mode) if mode == "warning" => DumpMode::Warning,
+            Some(mode) if mode == "file" => DumpMode::File,
+            Some(mode) => bail!(
+                mode.span(),
+                "Invalid dump mode. \
+                The following values are available: \
+                `warning`, \
+                `file`"
+            ),
+        };
+        Ok(Self {
+            mode,
+            span: args.name_span,
+        })
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum DumpMode {
+    Panic,
+    Warning,
+    File,
+}
+
+pub fn repr_int_type(attrs: &[Attribute]) -> Result<Option<Type>> {
+    const INT_TYPES: &[&str] = &[
+        "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+    ];
+    for attr in attrs {
+        if attr.path().is_ident("repr") {
+            let mut int_type = None;
+            attr.parse_nested_meta(|meta| {
+                if let Some(ident) = meta.path.get_ident() {
+                    if INT_TYPES.iter().any(|ty| ident == ty) {
+                        int_type = Some(ident.clone());
+                    }
+                }
+                // Skip arguments of other reprs such as `align(2)`.
+                if meta.input.peek(token::Paren) {
+                    let _args: TokenTree = meta.input.parse()?;
+                }
+                Ok(())
+            })?;
+            if let Some(int_type) = int_type {
+                return Ok(Some(parse_quote!(#int_type)));
+            }
+        }
+    }
+    Ok(None)
+}
+
+pub fn eval_int_expr(expr: &Expr) -> Option<i128> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        }) => lit.base10_parse().ok(),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => eval_int_expr(expr)?.checked_neg(),
+        Expr::Paren(ExprParen { expr, .. }) | Expr::Group(ExprGroup { expr, .. }) => {
+            eval_int_expr(expr)
+        }
+        _ => None,
+    }
+}
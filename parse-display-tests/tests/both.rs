@@ -22,6 +22,94 @@ fn both_struct_format() {
     assert_both("12,50", TestStruct { a: 12, b: 50 });
 }
 
+#[test]
+fn both_format_spec() {
+    #[derive(Display, FromStr, Debug, PartialEq)]
+    #[display("{name:>6}|{id:#06x}|{mask:08b}|{value:e}")]
+    struct TestStruct {
+        name: String,
+        id: u32,
+        mask: u8,
+        value: u64,
+    }
+    assert_both(
+        "    ab|0x00ff|00001010|1.5e6",
+        TestStruct {
+            name: "ab".into(),
+            id: 255,
+            mask: 10,
+            value: 1_500_000,
+        },
+    );
+}
+
+#[test]
+fn both_quote() {
+    #[derive(Display, FromStr, Debug, PartialEq)]
+    #[display("{a},{b}")]
+    struct TestStruct {
+        #[display(quote)]
+        a: String,
+        #[display(quote)]
+        b: String,
+    }
+    for a in ["", ",", r#"""#, r#"\"#, r#"a,"b"\"#] {
+        let value = TestStruct {
+            a: a.into(),
+            b: "x,y".into(),
+        };
+        assert_both(&value.to_string(), value);
+    }
+}
+
+#[test]
+fn both_fill_only_value() {
+    #[derive(Display, FromStr, Debug, PartialEq)]
+    #[display("{0:0>4}")]
+    struct TestNewType(u32);
+    assert_both("0000", TestNewType(0));
+    assert_both("0010", TestNewType(10));
+
+    #[derive(Display, FromStr, Debug, PartialEq)]
+    #[display("{a:0>4}|{b:x^5}")]
+    #[from_str(infer_regex = false)]
+    struct TestStruct {
+        a: u32,
+        b: String,
+    }
+    let value = TestStruct {
+        a: 0,
+        b: "x".into(),
+    };
+    assert_both("0000|xxxxx", value);
+}
+
+#[test]
+fn both_fill_only_value_width_arg() {
+    #[derive(Display, FromStr, Debug, PartialEq)]
+    #[display("{w}:{a:0>w$}")]
+    #[from_str(infer_regex = false)]
+    struct TestStruct {
+        a: u32,
+        w: usize,
+    }
+    assert_both("4:0000", TestStruct { a: 0, w: 4 });
+}
+
+#[test]
+fn both_discriminant_format_spec() {
+    #[derive(Display, FromStr, Debug, PartialEq)]
+    #[display("{discriminant:#x}", discriminant)]
+    #[repr(u8)]
+    enum TestEnum {
+        A = 0x1f,
+        B = 0xa0,
+    }
+    assert_both("0x1f", TestEnum::A);
+    assert_both("0xa0", TestEnum::B);
+    assert!("31".parse::<TestEnum>().is_err());
+}
+
 fn assert_both<T: Display + FromStr + PartialEq + Debug>(s: &str, value: T)
 where
     <T as FromStr>::Err: Display,
@@ -3,6 +3,7 @@
 use parse_display::*;
 
 #[test]
+#[allow(dead_code)]
 fn clippy_use_self() {
     #[derive(FromStr)]
     enum Foo {
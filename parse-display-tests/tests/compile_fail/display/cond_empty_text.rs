@@ -0,0 +1,10 @@
+use parse_display::{Display, FromStr};
+
+#[derive(Display, FromStr)]
+#[display("{name}{?admin:}")]
+struct TestStruct {
+    name: String,
+    admin: bool,
+}
+
+fn main() {}
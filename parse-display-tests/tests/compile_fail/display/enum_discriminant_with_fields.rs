@@ -0,0 +1,10 @@
+use parse_display::Display;
+
+#[derive(Display)]
+#[display(discriminant)]
+enum TestEnum {
+    A,
+    B(u32),
+}
+
+fn main() {}
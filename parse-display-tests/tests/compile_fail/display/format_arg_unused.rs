@@ -0,0 +1,9 @@
+use parse_display::Display;
+
+#[derive(Display)]
+#[display("{a}", sytle = "snake_case")]
+struct TestStruct {
+    a: u32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use parse_display::Display;
+
+#[derive(Display)]
+#[display(unit = "m")]
+struct TestStruct {
+    a: u32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use parse_display::Display;
+
+#[derive(Display)]
+#[display(style = "snake_case", digits = "xxx")]
+enum TestEnum {
+    A,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use parse_display::Display;
+
+#[derive(Display)]
+#[display("{0:y}")]
+struct TestStruct<T>(T);
+
+fn main() {}
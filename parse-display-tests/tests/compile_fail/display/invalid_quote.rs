@@ -0,0 +1,10 @@
+use parse_display::Display;
+
+#[derive(Display)]
+#[display("{a}")]
+struct TestStruct {
+    #[display(quote = "<>")]
+    a: u32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use parse_display::Display;
+
+#[derive(Display)]
+#[display(style = "xxx")]
+enum TestEnum {
+    A,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use parse_display::Display;
+
+#[derive(Display)]
+#[display(kv(sep = ""))]
+struct TestStruct {
+    a: u32,
+    b: u32,
+}
+
+fn main() {}
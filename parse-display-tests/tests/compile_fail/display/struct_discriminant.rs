@@ -0,0 +1,7 @@
+use parse_display::Display;
+
+#[derive(Display)]
+#[display(discriminant)]
+struct TestStruct(u32);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use parse_display::Display;
+
+#[derive(Display)]
+struct TestStruct {
+    a: u32,
+    b: u32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use parse_display::Display;
+
+#[derive(Display)]
+union TestUnion {
+    a: u32,
+    b: f32,
+}
+
+fn main() {}
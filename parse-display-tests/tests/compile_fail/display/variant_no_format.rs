@@ -0,0 +1,8 @@
+use parse_display::Display;
+
+#[derive(Display)]
+enum TestEnum {
+    A(u32),
+}
+
+fn main() {}
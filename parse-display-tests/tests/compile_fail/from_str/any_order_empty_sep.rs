@@ -0,0 +1,11 @@
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+#[display("a={a} b={b}")]
+#[from_str(any_order(sep = ""))]
+struct TestStruct {
+    a: u32,
+    b: u32,
+}
+
+fn main() {}
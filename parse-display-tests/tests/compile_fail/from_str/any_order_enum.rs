@@ -0,0 +1,10 @@
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+#[from_str(any_order)]
+enum TestEnum {
+    #[display("a={a} b={b}")]
+    A { a: u32, b: u32 },
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+#[display("a={a} {b}")]
+#[from_str(any_order)]
+struct TestStruct {
+    a: u32,
+    b: u32,
+}
+
+fn main() {}
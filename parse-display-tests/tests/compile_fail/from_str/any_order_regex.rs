@@ -0,0 +1,9 @@
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+#[from_str(regex = "(?<a>.*)", any_order)]
+struct TestStruct {
+    a: u32,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+#![deny(deprecated)]
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+#[display("{a}-{b}")]
+#[from_str(dump(warning))]
+struct TestStruct {
+    a: u32,
+    b: u32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+#[display("{name}{?admin:!}{?admin:!}")]
+struct TestStruct {
+    name: String,
+    admin: bool,
+}
+
+fn main() {}
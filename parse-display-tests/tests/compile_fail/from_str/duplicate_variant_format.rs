@@ -0,0 +1,11 @@
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+enum TestEnum {
+    #[display("a")]
+    A,
+    #[display("a")]
+    B,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+#[display(discriminant)]
+enum TestEnum {
+    A,
+    B(u32),
+}
+
+fn main() {}
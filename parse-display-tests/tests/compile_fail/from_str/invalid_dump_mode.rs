@@ -0,0 +1,10 @@
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+#[display("{a}")]
+#[from_str(dump(stdout))]
+struct TestStruct {
+    a: u32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+#[from_str(match = "shortest")]
+enum TestEnum {
+    A,
+}
+
+fn main() {}
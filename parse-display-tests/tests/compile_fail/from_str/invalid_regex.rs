@@ -0,0 +1,9 @@
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+#[from_str(regex = "(?<a>[0-9]+")]
+struct TestStruct {
+    a: u32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+enum TestEnum {
+    #[display(kv)]
+    A { a: u32 },
+}
+
+fn main() {}
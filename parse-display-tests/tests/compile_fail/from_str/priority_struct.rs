@@ -0,0 +1,8 @@
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+#[display("a")]
+#[from_str(priority = 1)]
+struct TestStruct;
+
+fn main() {}
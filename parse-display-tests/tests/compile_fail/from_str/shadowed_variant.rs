@@ -0,0 +1,19 @@
+#![deny(deprecated)]
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+enum TestEnum {
+    #[from_str(regex = "[a-z]+")]
+    A,
+    #[display("{0}")]
+    #[from_str(regex = "(?<0>[a-z]+)")]
+    B(String),
+    #[from_str(regex = "1|2")]
+    C,
+    #[display("1")]
+    One,
+    #[display("2")]
+    Two,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+#[display(discriminant)]
+struct TestStruct(u32);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+#[display("{#x}={a}")]
+struct TestStruct {
+    a: u32,
+}
+
+fn main() {}
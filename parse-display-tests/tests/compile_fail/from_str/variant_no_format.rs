@@ -0,0 +1,8 @@
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+enum TestEnum {
+    A(u32),
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+#[display("{a}")]
+struct TestStruct {
+    #[from_str(whitespace = "flexible")]
+    a: u32,
+}
+
+fn main() {}
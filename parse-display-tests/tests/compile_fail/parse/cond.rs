@@ -0,0 +1,5 @@
+use parse_display::parse;
+
+fn main() {
+    let _: Result<(bool,), _> = parse!("{?0:x}", "x");
+}
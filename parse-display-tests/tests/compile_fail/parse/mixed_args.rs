@@ -0,0 +1,6 @@
+use parse_display::parse;
+
+fn main() {
+    let mut a = 0u32;
+    let _: Result<(u32,), _> = parse!("{a}-{}", "1-2");
+}
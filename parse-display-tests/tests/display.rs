@@ -70,6 +70,85 @@ fn display_struct_field_with_parameter() {
     assert_display(TestStruct { a: 1, b: 2 }, "1   ,2");
 }
 
+#[test]
+fn display_struct_field_name() {
+    #[derive(Display)]
+    #[display("{#a}={a} {#b}={b}")]
+    struct TestStruct {
+        a: u32,
+        b: u32,
+    }
+
+    assert_display(TestStruct { a: 1, b: 2 }, "a=1 b=2");
+}
+
+#[test]
+fn display_struct_field_name_style() {
+    #[derive(Display)]
+    #[display("{#first_name}: {first_name}, {#fn}: {fn}", style = "kebab-case")]
+    struct TestStruct {
+        first_name: &'static str,
+        r#fn: u32,
+    }
+
+    assert_display(
+        TestStruct {
+            first_name: "alice",
+            r#fn: 1,
+        },
+        "first-name: alice, fn: 1",
+    );
+}
+
+#[test]
+fn display_struct_field_name_tuple() {
+    #[derive(Display)]
+    #[display("{#0}={0},{#1}={1}")]
+    struct TestStruct(u32, u32);
+
+    assert_display(TestStruct(5, 6), "0=5,1=6");
+}
+
+#[test]
+fn display_struct_kv() {
+    #[derive(Display)]
+    #[display(kv)]
+    struct TestStruct {
+        a: u32,
+        b: &'static str,
+    }
+
+    assert_display(TestStruct { a: 1, b: "x" }, "a=1 b=x");
+}
+
+#[test]
+fn display_struct_kv_sep_assign() {
+    #[derive(Display)]
+    #[display(kv(sep = ", ", assign = ": "), style = "kebab-case")]
+    struct TestStruct {
+        max_width: u32,
+        #[display("{:>3}")]
+        max_height: u32,
+    }
+
+    assert_display(
+        TestStruct {
+            max_width: 1,
+            max_height: 2,
+        },
+        "max-width: 1, max-height:   2",
+    );
+}
+
+#[test]
+fn display_struct_kv_brace() {
+    #[derive(Display)]
+    #[display(kv(sep = "}{", assign = "{"))]
+    struct TestStruct(u32, u32);
+
+    assert_display(TestStruct(1, 2), "0{1}{1{2");
+}
+
 #[test]
 fn display_struct_nested_field() {
     #[derive(Display)]
@@ -229,6 +308,61 @@ fn display_tuple_struct_attribute() {
     assert_display(TestStruct(10, 20), "AAA-10,20");
 }
 
+#[test]
+fn display_struct_cond() {
+    #[derive(Display)]
+    #[display("{name}{?admin: (admin)}")]
+    struct TestStruct {
+        name: &'static str,
+        admin: bool,
+    }
+    assert_display(
+        TestStruct {
+            name: "alice",
+            admin: true,
+        },
+        "alice (admin)",
+    );
+    assert_display(
+        TestStruct {
+            name: "bob",
+            admin: false,
+        },
+        "bob",
+    );
+}
+
+#[test]
+fn display_struct_cond_field_format() {
+    #[derive(Display)]
+    #[display("{0}{1}")]
+    struct TestStruct(u32, #[display("{?:!}")] bool);
+    assert_display(TestStruct(1, true), "1!");
+    assert_display(TestStruct(1, false), "1");
+}
+
+#[test]
+fn display_enum_cond() {
+    #[derive(Display)]
+    enum TestEnum {
+        #[display("{}{?0:*}")]
+        A(bool),
+        #[display("{}{?x.y:*}")]
+        B { x: TestStruct },
+    }
+    struct TestStruct {
+        y: bool,
+    }
+    assert_display(TestEnum::A(true), "A*");
+    assert_display(TestEnum::A(false), "A");
+    assert_display(
+        TestEnum::B {
+            x: TestStruct { y: true },
+        },
+        "B*",
+    );
+}
+
 #[test]
 fn display_enum() {
     #[derive(Display)]
@@ -440,6 +574,226 @@ fn display_enum_upper_title_case_head() {
     assert_display(TestEnum::_Xxx, "Xxx");
 }
 
+#[test]
+fn display_enum_dot_case() {
+    #[derive(Display)]
+    #[display(style = "dot.case")]
+    enum TestEnum {
+        AbcDef,
+        XyzXyz,
+        Abc1,
+        Abc1Abc2,
+        Xxx1xxx,
+        _Xxx,
+    }
+    assert_display(TestEnum::AbcDef, "abc.def");
+    assert_display(TestEnum::XyzXyz, "xyz.xyz");
+    assert_display(TestEnum::Abc1, "abc1");
+    assert_display(TestEnum::Abc1Abc2, "abc1.abc2");
+    assert_display(TestEnum::Xxx1xxx, "xxx1xxx");
+    assert_display(TestEnum::_Xxx, "xxx");
+}
+
+#[test]
+fn display_enum_train_case() {
+    #[derive(Display)]
+    #[display(style = "Train-Case")]
+    enum TestEnum {
+        AbcDef,
+        XyzXyz,
+        Abc1,
+        Abc1Abc2,
+        Xxx1xxx,
+        _Xxx,
+    }
+    assert_display(TestEnum::AbcDef, "Abc-Def");
+    assert_display(TestEnum::XyzXyz, "Xyz-Xyz");
+    assert_display(TestEnum::Abc1, "Abc1");
+    assert_display(TestEnum::Abc1Abc2, "Abc1-Abc2");
+    assert_display(TestEnum::Xxx1xxx, "Xxx1xxx");
+    assert_display(TestEnum::_Xxx, "Xxx");
+}
+
+#[test]
+fn display_enum_path_case() {
+    #[derive(Display)]
+    #[display(style = "path/case")]
+    enum TestEnum {
+        AbcDef,
+        XyzXyz,
+        Abc1,
+        Abc1Abc2,
+        Xxx1xxx,
+        _Xxx,
+    }
+    assert_display(TestEnum::AbcDef, "abc/def");
+    assert_display(TestEnum::XyzXyz, "xyz/xyz");
+    assert_display(TestEnum::Abc1, "abc1");
+    assert_display(TestEnum::Abc1Abc2, "abc1/abc2");
+    assert_display(TestEnum::Xxx1xxx, "xxx1xxx");
+    assert_display(TestEnum::_Xxx, "xxx");
+}
+
+#[test]
+fn display_enum_cobol_case() {
+    #[derive(Display)]
+    #[display(style = "COBOL-CASE")]
+    enum TestEnum {
+        AbcDef,
+        XyzXyz,
+        Abc1,
+        Abc1Abc2,
+        Xxx1xxx,
+        _Xxx,
+    }
+    assert_display(TestEnum::AbcDef, "ABC-DEF");
+    assert_display(TestEnum::XyzXyz, "XYZ-XYZ");
+    assert_display(TestEnum::Abc1, "ABC1");
+    assert_display(TestEnum::Abc1Abc2, "ABC1-ABC2");
+    assert_display(TestEnum::Xxx1xxx, "XXX1XXX");
+    assert_display(TestEnum::_Xxx, "XXX");
+}
+
+#[test]
+fn display_enum_ada_case() {
+    #[derive(Display)]
+    #[display(style = "Ada_Case")]
+    enum TestEnum {
+        AbcDef,
+        XyzXyz,
+        Abc1,
+        Abc1Abc2,
+        Xxx1xxx,
+        _Xxx,
+    }
+    assert_display(TestEnum::AbcDef, "Abc_Def");
+    assert_display(TestEnum::XyzXyz, "Xyz_Xyz");
+    assert_display(TestEnum::Abc1, "Abc1");
+    assert_display(TestEnum::Abc1Abc2, "Abc1_Abc2");
+    assert_display(TestEnum::Xxx1xxx, "Xxx1xxx");
+    assert_display(TestEnum::_Xxx, "Xxx");
+}
+
+#[test]
+fn display_enum_style_acronym() {
+    #[derive(Display)]
+    #[display(style = "snake_case")]
+    #[allow(clippy::upper_case_acronyms)]
+    enum TestEnum {
+        HTTPServer,
+        GetHTTP,
+        IOError,
+        A,
+        ABC,
+    }
+    assert_display(TestEnum::HTTPServer, "http_server");
+    assert_display(TestEnum::GetHTTP, "get_http");
+    assert_display(TestEnum::IOError, "io_error");
+    assert_display(TestEnum::A, "a");
+    assert_display(TestEnum::ABC, "abc");
+}
+
+#[test]
+fn display_enum_style_acronym_camel_case() {
+    #[derive(Display)]
+    #[display(style = "camelCase")]
+    enum TestEnum {
+        HTTPServer,
+        GetHTTPResponse,
+    }
+    assert_display(TestEnum::HTTPServer, "httpServer");
+    assert_display(TestEnum::GetHTTPResponse, "getHttpResponse");
+}
+
+#[test]
+fn display_enum_style_digits_suffix() {
+    #[derive(Display)]
+    #[display(style = "snake_case", digits = "suffix")]
+    enum TestEnum {
+        Var2D,
+        Var2d,
+        Abc12Def,
+        X86_64,
+    }
+    assert_display(TestEnum::Var2D, "var2_d");
+    assert_display(TestEnum::Var2d, "var2d");
+    assert_display(TestEnum::Abc12Def, "abc12_def");
+    assert_display(TestEnum::X86_64, "x86_64");
+}
+
+#[test]
+fn display_enum_style_digits_prefix() {
+    #[derive(Display)]
+    #[display(style = "snake_case", digits = "prefix")]
+    enum TestEnum {
+        Var2D,
+        Var2d,
+        Abc12Def,
+        X86Of64,
+    }
+    assert_display(TestEnum::Var2D, "var_2d");
+    assert_display(TestEnum::Var2d, "var_2d");
+    assert_display(TestEnum::Abc12Def, "abc_12def");
+    assert_display(TestEnum::X86Of64, "x_86of_64");
+}
+
+#[test]
+fn display_enum_style_digits_separate() {
+    #[derive(Display)]
+    #[display(style = "snake_case")]
+    enum TestEnum {
+        #[display(digits = "separate")]
+        Var2D,
+        #[display(digits = "separate")]
+        Abc12def,
+        Abc12def3,
+    }
+    assert_display(TestEnum::Var2D, "var_2_d");
+    assert_display(TestEnum::Abc12def, "abc_12_def");
+    assert_display(TestEnum::Abc12def3, "abc12def3");
+}
+
+#[test]
+fn display_enum_style_unicode() {
+    #[derive(Display)]
+    #[display(style = "snake_case")]
+    enum TestEnum {
+        Über,
+        ÉtatInitial,
+        ÄÖÜStraße,
+    }
+    assert_display(TestEnum::Über, "über");
+    assert_display(TestEnum::ÉtatInitial, "état_initial");
+    assert_display(TestEnum::ÄÖÜStraße, "äöü_straße");
+}
+
+#[test]
+fn display_enum_style_unicode_upper() {
+    #[derive(Display)]
+    #[display(style = "SNAKE_CASE")]
+    enum TestEnum {
+        Über,
+        ÉtatInitial,
+        StraßeNord,
+    }
+    assert_display(TestEnum::Über, "ÜBER");
+    assert_display(TestEnum::ÉtatInitial, "ÉTAT_INITIAL");
+    assert_display(TestEnum::StraßeNord, "STRASSE_NORD");
+}
+
+#[test]
+fn display_enum_style_unicode_camel_case() {
+    #[derive(Display)]
+    #[display(style = "camelCase")]
+    enum TestEnum {
+        ÉtatInitial,
+        #[display(style = "Title Case")]
+        ÜberAlles,
+    }
+    assert_display(TestEnum::ÉtatInitial, "étatInitial");
+    assert_display(TestEnum::ÜberAlles, "Über Alles");
+}
+
 #[test]
 fn display_enum_lower_case() {
     #[derive(Display)]
@@ -500,6 +854,24 @@ fn display_enum_none() {
     assert_display(TestEnum::_Xxx, "_Xxx");
 }
 
+#[test]
+fn display_enum_field_name() {
+    #[derive(Display)]
+    #[display(style = "SNAKE_CASE")]
+    enum TestEnum {
+        #[display("{}({#max_value}={max_value})")]
+        LimitValue { max_value: u32 },
+        #[display("{}({#x_pos})", style = "Title Case")]
+        Position { x_pos: u32 },
+    }
+
+    assert_display(
+        TestEnum::LimitValue { max_value: 5 },
+        "LIMIT_VALUE(MAX_VALUE=5)",
+    );
+    assert_display(TestEnum::Position { x_pos: 5 }, "Position(X Pos)");
+}
+
 #[test]
 fn display_enum_common_format() {
     #[derive(Display)]
@@ -644,6 +1016,48 @@ fn display_enum_field_format_deep_noncopy() {
     );
 }
 
+#[test]
+fn display_enum_discriminant() {
+    #[derive(Display)]
+    #[display(discriminant)]
+    enum TestEnum {
+        Ok = 200,
+        NotFound = 404,
+        Next,
+        Negative = -1,
+    }
+    assert_display(TestEnum::Ok, "200");
+    assert_display(TestEnum::NotFound, "404");
+    assert_display(TestEnum::Next, "405");
+    assert_display(TestEnum::Negative, "-1");
+}
+
+#[test]
+fn display_enum_discriminant_format() {
+    #[derive(Display)]
+    #[display("{}:{discriminant}", discriminant)]
+    enum TestEnum {
+        A = 1,
+        #[display("b{discriminant:03}")]
+        B = 2,
+    }
+    assert_display(TestEnum::A, "A:1");
+    assert_display(TestEnum::B, "b002");
+}
+
+#[test]
+fn display_enum_discriminant_repr() {
+    #[derive(Display)]
+    #[display("{discriminant:#x}", discriminant)]
+    #[repr(u8)]
+    enum TestEnum {
+        A = 0x10,
+        B = 0xff,
+    }
+    assert_display(TestEnum::A, "0x10");
+    assert_display(TestEnum::B, "0xff");
+}
+
 #[test]
 fn auto_bound_newtype() {
     #[derive(Display)]
@@ -828,6 +1242,7 @@ fn bound_type_generic_x2() {
     struct Inner<T>(T);
 }
 
+#[allow(dead_code)]
 #[test]
 fn bound_type_array() {
     #[derive(Display)]
@@ -866,7 +1281,8 @@ fn bound_by_hand_with_auto() {
     assert_display(Outer(Inner(10), 20), "10,20");
 }
 
-#[deny(private_in_public)]
+#[allow(dead_code)]
+#[deny(private_interfaces)]
 #[test]
 fn bound_struct_field() {
     #[derive(Display)]
@@ -966,6 +1382,7 @@ fn macro_rule_hygiene() {
     assert_display(HygieneTestType { x: 5 }, "5");
 }
 
+#[allow(dead_code)]
 #[test]
 fn format_spec_is_empty() {
     #[derive(Display)]
@@ -984,6 +1401,204 @@ fn dst_field() {
     assert_display(x, "abc");
 }
 
+#[test]
+fn format_arg_path() {
+    #[derive(Display)]
+    #[display("{a}{Self::UNIT}")]
+    struct TestStruct {
+        a: u32,
+    }
+    impl TestStruct {
+        const UNIT: &'static str = "m";
+    }
+    assert_display(TestStruct { a: 10 }, "10m");
+}
+
+#[test]
+fn format_arg_named() {
+    #[derive(Display)]
+    #[display("{a} {unit:>3}", unit = Self::UNIT)]
+    struct TestStruct {
+        a: u32,
+    }
+    impl TestStruct {
+        const UNIT: &'static str = "m";
+    }
+    assert_display(TestStruct { a: 10 }, "10   m");
+}
+
+#[test]
+fn format_arg_named_expr() {
+    #[derive(Display)]
+    #[display("{x}-{y}", x = 1 + 2, y = "a")]
+    struct TestStruct;
+    assert_display(TestStruct, "3-a");
+}
+
+#[test]
+fn format_arg_variant() {
+    #[derive(Display)]
+    enum TestEnum {
+        #[display("{0}{unit}", unit = Self::UNIT)]
+        A(u32),
+        #[display("{0}{Self::UNIT}")]
+        B(u32),
+    }
+    impl TestEnum {
+        const UNIT: &'static str = "kg";
+    }
+    assert_display(TestEnum::A(1), "1kg");
+    assert_display(TestEnum::B(2), "2kg");
+}
+
+#[test]
+fn format_arg_field() {
+    #[derive(Display)]
+    struct TestStruct {
+        #[display("{}{unit}", unit = "cm")]
+        a: u32,
+    }
+    assert_display(TestStruct { a: 5 }, "5cm");
+}
+
+#[test]
+fn format_spec_width_field() {
+    #[derive(Display)]
+    #[display("[{a:>width$}]")]
+    struct TestStruct {
+        a: u32,
+        width: usize,
+    }
+    assert_display(TestStruct { a: 5, width: 3 }, "[  5]");
+}
+
+#[test]
+fn format_spec_precision_field() {
+    #[derive(Display)]
+    #[display("{value:.prec$}")]
+    struct TestStruct {
+        value: f64,
+        prec: usize,
+    }
+    assert_display(
+        TestStruct {
+            value: 1.2345,
+            prec: 2,
+        },
+        "1.23",
+    );
+}
+
+#[test]
+fn format_spec_width_and_precision_field() {
+    #[derive(Display)]
+    #[display("{value:>width$.prec$}|{prec:>width$}")]
+    struct TestStruct {
+        value: f64,
+        width: usize,
+        prec: usize,
+    }
+    assert_display(
+        TestStruct {
+            value: 1.2345,
+            width: 6,
+            prec: 1,
+        },
+        "   1.2|     1",
+    );
+}
+
+#[test]
+fn format_spec_width_index() {
+    #[derive(Display)]
+    #[display("{0:>1$}")]
+    struct TestStruct(u32, usize);
+    assert_display(TestStruct(5, 3), "  5");
+}
+
+#[test]
+fn format_spec_width_variant() {
+    #[derive(Display)]
+    enum TestEnum {
+        #[display("{a:>width$}")]
+        A { a: u32, width: usize },
+    }
+    assert_display(TestEnum::A { a: 5, width: 2 }, " 5");
+}
+
+#[test]
+fn format_spec_width_named_arg() {
+    #[derive(Display)]
+    #[display("{a:>w$}", w = 4)]
+    struct TestStruct {
+        a: u32,
+    }
+    assert_display(TestStruct { a: 5 }, "   5");
+}
+
+#[test]
+fn format_spec_width_in_field_format() {
+    #[derive(Display)]
+    #[display("{a}")]
+    struct TestStruct {
+        #[display("[{:>width$}]")]
+        a: u32,
+        width: usize,
+    }
+    assert_display(TestStruct { a: 5, width: 3 }, "[  5]");
+}
+
+#[test]
+fn quote_field() {
+    #[derive(Display)]
+    #[display("{a},{b}")]
+    struct TestStruct {
+        #[display(quote)]
+        a: &'static str,
+        b: u32,
+    }
+    assert_display(TestStruct { a: "x", b: 1 }, r#""x",1"#);
+    assert_display(
+        TestStruct {
+            a: r#"a,"b"\c"#,
+            b: 1,
+        },
+        r#""a,\"b\"\\c",1"#,
+    );
+}
+
+#[test]
+fn quote_char() {
+    #[derive(Display)]
+    #[display("{a}")]
+    struct TestStruct {
+        #[display(quote = "'")]
+        a: &'static str,
+    }
+    assert_display(TestStruct { a: r#"it's "x""# }, r#"'it\'s "x"'"#);
+}
+
+#[test]
+fn quote_field_format() {
+    #[derive(Display)]
+    #[display("{a}")]
+    struct TestStruct {
+        #[display("[{}]", quote)]
+        a: u32,
+    }
+    assert_display(TestStruct { a: 1 }, r#"["1"]"#);
+}
+
+#[test]
+fn quote_variant() {
+    #[derive(Display)]
+    enum TestEnum {
+        #[display("{0}")]
+        A(#[display(quote)] &'static str),
+    }
+    assert_display(TestEnum::A("x"), r#""x""#);
+}
+
 fn assert_display<T: core::fmt::Display>(value: T, display: &str) {
     let value_display = alloc::format!("{value}");
     assert_eq!(value_display, display);
@@ -0,0 +1,164 @@
+use parse_display::*;
+use std::fmt::Debug;
+
+#[test]
+fn fields_by_name_struct() {
+    #[derive(FieldsByName, Debug, PartialEq)]
+    struct TestStruct {
+        a: u32,
+        b: String,
+    }
+    let value = TestStruct {
+        a: 1,
+        b: "x".into(),
+    };
+    assert_eq!(TestStruct::FIELD_NAMES, &["a", "b"]);
+    assert_format("{a}-{b}", "1-x", value);
+    let value = TestStruct {
+        a: 1,
+        b: "x".into(),
+    };
+    assert_format("b={b}, a={a}", "b=x, a=1", value);
+}
+
+#[test]
+fn fields_by_name_tuple() {
+    #[derive(FieldsByName, Debug, PartialEq)]
+    struct TestStruct(u32, u32);
+    assert_format("{1}:{0}", "2:1", TestStruct(1, 2));
+}
+
+#[test]
+fn fields_by_name_escape() {
+    #[derive(FieldsByName, Debug, PartialEq)]
+    struct TestStruct {
+        a: u32,
+    }
+    assert_format("{{{a}}}", "{1}", TestStruct { a: 1 });
+}
+
+#[test]
+fn fields_by_name_keyword() {
+    #[derive(FieldsByName, Debug, PartialEq)]
+    struct TestStruct {
+        r#fn: u32,
+    }
+    assert_format("fn {fn}", "fn 1", TestStruct { r#fn: 1 });
+}
+
+#[test]
+fn fields_by_name_field_format() {
+    #[derive(FieldsByName, Debug, PartialEq)]
+    struct TestStruct {
+        #[display("[{}]")]
+        a: u32,
+    }
+    assert_format("a={a}", "a=[1]", TestStruct { a: 1 });
+}
+
+#[test]
+fn fields_by_name_field_attrs() {
+    #[derive(FieldsByName, Debug, PartialEq)]
+    struct TestStruct {
+        #[display("<{}>")]
+        a: u32,
+        #[display(quote)]
+        b: String,
+        #[display("{:x}")]
+        c: u8,
+    }
+    let value = TestStruct {
+        a: 5,
+        b: "x;y".into(),
+        c: 31,
+    };
+    assert_format("{a};{c};{b}", r#"<5>;1f;"x;y""#, value);
+}
+
+#[test]
+fn fields_by_name_default() {
+    #[derive(FieldsByName, Debug, PartialEq)]
+    struct TestStruct {
+        a: u32,
+        #[from_str(default)]
+        b: u32,
+        #[from_str(default = a + 10)]
+        c: u32,
+    }
+    let format = Format::<TestStruct>::new("{a}").unwrap();
+    assert_eq!(format.parse("1"), Ok(TestStruct { a: 1, b: 0, c: 11 }));
+
+    let format = Format::<TestStruct>::new("{b}").unwrap();
+    assert_eq!(
+        format.parse("1"),
+        Err(ParseError::with_message("field `a` is missing."))
+    );
+}
+
+#[test]
+fn fields_by_name_struct_default() {
+    #[derive(FieldsByName, Debug, PartialEq)]
+    #[from_str(default)]
+    struct TestStruct {
+        a: u32,
+        b: u32,
+    }
+    impl Default for TestStruct {
+        fn default() -> Self {
+            Self { a: 5, b: 6 }
+        }
+    }
+    let format = Format::<TestStruct>::new("{b}").unwrap();
+    assert_eq!(format.parse("1"), Ok(TestStruct { a: 5, b: 1 }));
+}
+
+#[test]
+fn fields_by_name_generic() {
+    #[derive(FieldsByName, Debug, PartialEq)]
+    struct TestStruct<T> {
+        a: T,
+    }
+    assert_format("<{a}>", "<1>", TestStruct { a: 1 });
+}
+
+#[test]
+fn fields_by_name_error() {
+    #[derive(FieldsByName, Debug, PartialEq)]
+    struct TestStruct {
+        a: u32,
+    }
+    assert!(Format::<TestStruct>::new("{b}").is_err());
+    assert!(Format::<TestStruct>::new("{a").is_err());
+    assert!(Format::<TestStruct>::new("a}").is_err());
+
+    let format = Format::<TestStruct>::new("a={a}").unwrap();
+    assert!(format.parse("b=1").is_err());
+    assert!(format.parse("a=x").is_err());
+}
+
+#[test]
+fn fields_by_name_hygiene() {
+    #[derive(FieldsByName, Debug, PartialEq)]
+    struct TestStruct {
+        fields: u32,
+        s: u32,
+        value: u32,
+        e: u32,
+    }
+    assert_format(
+        "{fields},{s},{value},{e}",
+        "1,2,3,4",
+        TestStruct {
+            fields: 1,
+            s: 2,
+            value: 3,
+            e: 4,
+        },
+    );
+}
+
+fn assert_format<T: FieldsByName + PartialEq + Debug>(format: &str, s: &str, value: T) {
+    let format = Format::<T>::new(format).unwrap();
+    assert_eq!(format.display(&value).to_string(), s);
+    assert_eq!(format.parse(s), Ok(value), "input = \"{s}\"");
+}
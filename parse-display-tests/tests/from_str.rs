@@ -48,6 +48,311 @@ fn from_str_struct_format_swap() {
     assert_from_str("12,50", TestStruct { b: 12, a: 50 });
 }
 
+#[test]
+fn from_str_struct_field_name() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{#a}={a} {#max_b}={max_b}", style = "CamelCase")]
+    struct TestStruct {
+        a: u32,
+        max_b: u32,
+    }
+    assert_from_str("A=1 MaxB=2", TestStruct { a: 1, max_b: 2 });
+    assert_from_str_err::<TestStruct>("a=1 max_b=2");
+}
+
+#[test]
+fn from_str_struct_kv() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display(kv)]
+    struct TestStruct {
+        a: u32,
+        b: String,
+    }
+    let value = TestStruct {
+        a: 1,
+        b: "x".into(),
+    };
+    assert_from_str("a=1 b=x", value);
+    let value = TestStruct {
+        a: 1,
+        b: "x".into(),
+    };
+    assert_from_str("b=x a=1", value);
+    assert_from_str_err::<TestStruct>("a=1");
+    assert_from_str_err::<TestStruct>("a=1 c=2");
+    assert_from_str_err::<TestStruct>("a=x b=x");
+    assert_from_str_err::<TestStruct>("a=1b=x");
+    assert_eq!(
+        "a=1".parse::<TestStruct>(),
+        Err(ParseError::with_message("field `b` is missing."))
+    );
+}
+
+#[test]
+fn from_str_struct_kv_sep_assign() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display(kv(sep = ", ", assign = ": "), style = "kebab-case")]
+    struct TestStruct {
+        max_width: u32,
+        max_height: u32,
+    }
+    assert_from_str(
+        "max-height: 2, max-width: 1",
+        TestStruct {
+            max_width: 1,
+            max_height: 2,
+        },
+    );
+}
+
+#[test]
+fn from_str_struct_kv_value_contains_sep() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display(kv)]
+    struct TestStruct {
+        a: u32,
+        b: String,
+    }
+    assert_from_str(
+        "b=x y a=1",
+        TestStruct {
+            a: 1,
+            b: "x y".into(),
+        },
+    );
+    assert_from_str(
+        "b=x a=y a=1",
+        TestStruct {
+            a: 1,
+            b: "x a=y".into(),
+        },
+    );
+}
+
+#[test]
+fn from_str_struct_kv_duplicate_key() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display(kv)]
+    struct TestStruct {
+        a: u32,
+        b: String,
+    }
+    assert_from_str_err::<TestStruct>("b=x a=3 a=4");
+    assert_from_str_err::<TestStruct>("a=3 b=x b=y");
+    assert_eq!(
+        "a=3 a=4 b=x".parse::<TestStruct>(),
+        Err(ParseError::with_message(
+            "`a=` is specified more than once."
+        ))
+    );
+}
+
+#[test]
+fn from_str_struct_kv_default_field() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display(kv)]
+    struct TestStruct {
+        #[from_str(default)]
+        a: u32,
+        b: String,
+    }
+    assert_from_str(
+        "b=hello a=2",
+        TestStruct {
+            a: 2,
+            b: "hello".into(),
+        },
+    );
+    assert_from_str(
+        "b=hello",
+        TestStruct {
+            a: 0,
+            b: "hello".into(),
+        },
+    );
+}
+
+#[test]
+fn from_str_struct_kv_extra_sep() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display(kv)]
+    struct TestStruct {
+        a: u32,
+        b: u32,
+    }
+    assert_from_str_err::<TestStruct>("a=1 b=2 ");
+    assert_from_str_err::<TestStruct>(" a=1 b=2");
+    assert_from_str_err::<TestStruct>("a=1  b=2");
+}
+
+#[test]
+fn from_str_struct_kv_field_default() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display(kv)]
+    struct TestStruct {
+        a: u32,
+        #[from_str(default = 10)]
+        b: u32,
+        #[from_str(default)]
+        c: u32,
+    }
+    assert_from_str("a=1", TestStruct { a: 1, b: 10, c: 0 });
+    assert_from_str("c=3 a=1 b=2", TestStruct { a: 1, b: 2, c: 3 });
+    assert_from_str_err::<TestStruct>("b=2");
+}
+
+#[test]
+fn from_str_struct_kv_struct_default() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display(kv)]
+    #[from_str(default)]
+    struct TestStruct {
+        a: u32,
+        b: u32,
+    }
+    impl Default for TestStruct {
+        fn default() -> Self {
+            Self { a: 5, b: 6 }
+        }
+    }
+    assert_from_str("", TestStruct { a: 5, b: 6 });
+    assert_from_str("b=2", TestStruct { a: 5, b: 2 });
+    assert_from_str("b=2 a=1", TestStruct { a: 1, b: 2 });
+}
+
+#[test]
+fn from_str_struct_kv_field_regex() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display(kv)]
+    struct TestStruct {
+        #[from_str(regex = "[0-9]+")]
+        a: String,
+        b: u32,
+    }
+    assert_from_str(
+        "b=2 a=123",
+        TestStruct {
+            a: "123".into(),
+            b: 2,
+        },
+    );
+    assert_from_str_err::<TestStruct>("b=2 a=x");
+}
+
+#[test]
+fn from_str_struct_any_order() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("a={a} b={b}")]
+    #[from_str(any_order)]
+    struct TestStruct {
+        a: u32,
+        b: u32,
+    }
+    assert_from_str("a=1 b=2", TestStruct { a: 1, b: 2 });
+    assert_from_str("b=2 a=1", TestStruct { a: 1, b: 2 });
+    assert_from_str_err::<TestStruct>("a=1");
+    assert_from_str_err::<TestStruct>("a=1,b=2");
+    assert_from_str_err::<TestStruct>("a=1 b=2 a=3");
+    assert_from_str_err::<TestStruct>("a=1 b=2 ");
+}
+
+#[test]
+fn from_str_struct_any_order_sep() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("[{a}], <{b}>, c={c}")]
+    #[from_str(any_order(sep = ", "))]
+    struct TestStruct {
+        a: u32,
+        b: u32,
+        #[from_str(default)]
+        c: String,
+    }
+    assert_from_str(
+        "<2>, [1]",
+        TestStruct {
+            a: 1,
+            b: 2,
+            c: "".into(),
+        },
+    );
+    assert_from_str(
+        "c=xyz, <2>, [1]",
+        TestStruct {
+            a: 1,
+            b: 2,
+            c: "xyz".into(),
+        },
+    );
+}
+
+#[test]
+fn from_str_struct_any_order_literal_segment() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("a={a} verbose")]
+    #[from_str(any_order)]
+    struct TestStruct {
+        a: u32,
+    }
+    assert_from_str("a=1 verbose", TestStruct { a: 1 });
+    assert_from_str("verbose a=1", TestStruct { a: 1 });
+    assert_from_str_err::<TestStruct>("a=1");
+    assert_from_str_err::<TestStruct>("verbose a=1 verbose");
+    assert_eq!(
+        "a=1".parse::<TestStruct>(),
+        Err(ParseError::with_message("`verbose` is missing."))
+    );
+}
+
+#[test]
+fn from_str_struct_cond() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{name}{?admin: (admin)}")]
+    struct TestStruct {
+        name: String,
+        admin: bool,
+    }
+    assert_from_str(
+        "alice (admin)",
+        TestStruct {
+            name: "alice".into(),
+            admin: true,
+        },
+    );
+    assert_from_str(
+        "bob",
+        TestStruct {
+            name: "bob".into(),
+            admin: false,
+        },
+    );
+}
+
+#[test]
+fn from_str_struct_cond_field_format() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{0}{1}")]
+    struct TestStruct(u32, #[display("{?:!}")] bool);
+    assert_from_str("1!", TestStruct(1, true));
+    assert_from_str("1", TestStruct(1, false));
+    assert_from_str_err::<TestStruct>("1?");
+}
+
+#[test]
+fn from_str_enum_cond() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    enum TestEnum {
+        #[display("{}{?0:*}")]
+        A(bool),
+        #[display("{}-{?x:x}{?y:y}")]
+        B { x: bool, y: bool },
+    }
+    assert_from_str("A*", TestEnum::A(true));
+    assert_from_str("A", TestEnum::A(false));
+    assert_from_str("B-y", TestEnum::B { x: false, y: true });
+    assert_from_str("B-xy", TestEnum::B { x: true, y: true });
+    assert_from_str_err::<TestEnum>("B-yx");
+}
+
 #[test]
 fn from_str_struct_regex() {
     #[derive(FromStr, Debug, Eq, PartialEq)]
@@ -331,6 +636,128 @@ fn from_str_tuple_field_default() {
     assert_from_str("12", TestStruct(12, 0));
 }
 
+#[test]
+fn from_str_struct_field_default_expr() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{host}")]
+    struct TestStruct {
+        host: String,
+        #[from_str(default = 8080)]
+        port: u16,
+    }
+    assert_from_str(
+        "localhost",
+        TestStruct {
+            host: "localhost".into(),
+            port: 8080,
+        },
+    );
+}
+
+#[test]
+fn from_str_struct_field_default_expr_ref_field() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}")]
+    struct TestStruct {
+        #[from_str(default = a * 2)]
+        b: u32,
+        a: u32,
+        #[from_str(default = format!("{a}"))]
+        c: String,
+    }
+    assert_from_str(
+        "10",
+        TestStruct {
+            a: 10,
+            b: 20,
+            c: "10".into(),
+        },
+    );
+}
+
+#[test]
+fn from_str_tuple_field_default_expr() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{0}")]
+    struct TestStruct(u32, #[from_str(default = _0 + 1)] u32);
+    assert_from_str("10", TestStruct(10, 11));
+}
+
+#[test]
+fn from_str_struct_field_default_expr_in_format() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}")]
+    struct TestStruct {
+        #[from_str(default = 5)]
+        a: u32,
+    }
+    assert_from_str("10", TestStruct { a: 10 });
+}
+
+#[test]
+fn from_str_struct_default_expr() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{b}")]
+    #[from_str(default = Self::new())]
+    struct TestStruct {
+        a: u32,
+        b: u32,
+    }
+    impl TestStruct {
+        fn new() -> Self {
+            Self { a: 99, b: 99 }
+        }
+    }
+    assert_from_str("10", TestStruct { a: 99, b: 10 });
+}
+
+#[test]
+fn from_str_struct_default_expr_with_field_default_expr() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{b}")]
+    #[from_str(default = Self { a: 1, b: 2, c: 3 })]
+    struct TestStruct {
+        a: u32,
+        b: u32,
+        #[from_str(default = b + 1)]
+        c: u32,
+    }
+    assert_from_str("10", TestStruct { a: 1, b: 10, c: 11 });
+}
+
+#[test]
+fn from_str_field_default_expr_with_new() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}")]
+    #[from_str(new = Self { a, b })]
+    struct TestStruct {
+        a: u32,
+        #[from_str(default = a + 1)]
+        b: u32,
+    }
+    assert_from_str("10", TestStruct { a: 10, b: 11 });
+}
+
+#[test]
+fn from_str_enum_field_default_expr() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    enum TestEnum {
+        #[display("{}-{a}")]
+        A {
+            a: u32,
+            #[from_str(default = "x".to_string())]
+            b: String,
+        },
+    }
+    assert_from_str(
+        "A-1",
+        TestEnum::A {
+            a: 1,
+            b: "x".into(),
+        },
+    );
+}
+
 #[test]
 fn from_str_struct_default_fields_str() {
     #[derive(FromStr, Debug, Eq, PartialEq, Default)]
@@ -456,23 +883,75 @@ fn from_str_enum_style() {
 }
 
 #[test]
-fn from_str_enum_format() {
+fn from_str_enum_style_acronym_digits() {
     #[derive(FromStr, Debug, Eq, PartialEq)]
-    #[display("{}--")]
+    #[display(style = "Train-Case", digits = "prefix")]
     enum TestEnum {
-        A,
-        Bc,
+        HTTPServer,
+        Var2D,
     }
-    assert_from_str("A--", TestEnum::A);
-    assert_from_str("Bc--", TestEnum::Bc);
+    assert_from_str("Http-Server", TestEnum::HTTPServer);
+    assert_from_str("Var-2d", TestEnum::Var2D);
 }
 
 #[test]
-fn from_str_enum_format_struct_var() {
+fn from_str_enum_style_unicode() {
     #[derive(FromStr, Debug, Eq, PartialEq)]
-    #[display("{}--{x}")]
+    #[display(style = "kebab-case")]
     enum TestEnum {
-        A { x: u32 },
+        Über,
+        ÉtatInitial,
+    }
+    assert_from_str("über", TestEnum::Über);
+    assert_from_str("état-initial", TestEnum::ÉtatInitial);
+}
+
+#[test]
+fn from_str_enum_field_name() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display(style = "snake_case")]
+    enum TestEnum {
+        #[display("{}:{#max_value}={max_value}")]
+        LimitValue { max_value: u32 },
+    }
+    assert_from_str(
+        "limit_value:max_value=5",
+        TestEnum::LimitValue { max_value: 5 },
+    );
+}
+
+#[test]
+fn from_str_enum_variant_any_order() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    enum TestEnum {
+        #[display("x={x} y={y}")]
+        #[from_str(any_order)]
+        Point { x: i32, y: i32 },
+        #[display("{0}")]
+        Value(i32),
+    }
+    assert_from_str("y=2 x=1", TestEnum::Point { x: 1, y: 2 });
+    assert_from_str("5", TestEnum::Value(5));
+}
+
+#[test]
+fn from_str_enum_format() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{}--")]
+    enum TestEnum {
+        A,
+        Bc,
+    }
+    assert_from_str("A--", TestEnum::A);
+    assert_from_str("Bc--", TestEnum::Bc);
+}
+
+#[test]
+fn from_str_enum_format_struct_var() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{}--{x}")]
+    enum TestEnum {
+        A { x: u32 },
         Bc { x: u32 },
     }
     assert_from_str("A--10", TestEnum::A { x: 10 });
@@ -683,6 +1162,71 @@ fn from_str_enum_field_regex() {
     assert_from_str("yyy + mmm50", TestEnum::Bc { x: 50 });
 }
 
+#[test]
+fn from_str_enum_discriminant() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display(discriminant)]
+    enum TestEnum {
+        Ok = 200,
+        NotFound = 404,
+        Next,
+        Negative = -1,
+    }
+    assert_from_str("200", TestEnum::Ok);
+    assert_from_str("404", TestEnum::NotFound);
+    assert_from_str("405", TestEnum::Next);
+    assert_from_str("-1", TestEnum::Negative);
+    assert_from_str_err::<TestEnum>("Ok");
+    assert_from_str_err::<TestEnum>("201");
+}
+
+#[test]
+fn from_str_enum_discriminant_format() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{}:{discriminant}", discriminant)]
+    enum TestEnum {
+        A = 1,
+        #[display("b{discriminant:03}")]
+        B = 2,
+    }
+    assert_from_str("A:1", TestEnum::A);
+    assert_from_str("b002", TestEnum::B);
+    assert_from_str_err::<TestEnum>("A:2");
+    assert_from_str_err::<TestEnum>("b001");
+}
+
+#[test]
+fn from_str_enum_discriminant_const_expr() {
+    const BASE: u16 = 0x100;
+
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display(discriminant)]
+    #[repr(u16)]
+    enum TestEnum {
+        A = BASE,
+        B,
+        C = BASE * 2,
+    }
+    assert_from_str("256", TestEnum::A);
+    assert_from_str("257", TestEnum::B);
+    assert_from_str("512", TestEnum::C);
+    assert_from_str_err::<TestEnum>("258");
+}
+
+#[test]
+fn from_str_enum_discriminant_repr_with_args() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display(discriminant)]
+    #[repr(u8, align(2))]
+    enum TestEnum {
+        A = 255,
+        B = 1,
+    }
+    assert_from_str("255", TestEnum::A);
+    assert_from_str("1", TestEnum::B);
+    assert_from_str_err::<TestEnum>("256");
+}
+
 #[test]
 fn auto_bound_newtype() {
     #[derive(FromStr, Debug, Eq, PartialEq)]
@@ -748,6 +1292,7 @@ pub struct TestStructPrivateInPublicGeneric<T>(TestStructPrivateGeneric<T>);
 #[derive(FromStr, Debug, Eq, PartialEq)]
 struct TestStructPrivateGeneric<T>(T);
 
+#[allow(dead_code)]
 #[test]
 fn bound_predicate_struct() {
     #[derive(FromStr, Debug, Eq, PartialEq)]
@@ -767,7 +1312,7 @@ fn bound_predicate_struct() {
 
 #[test]
 fn different_bound() {
-    #![deny(private_in_public)]
+    #![deny(private_interfaces)]
 
     #[derive(Display, FromStr, PartialEq, Debug)]
     #[display(bound("T : Display"))]
@@ -793,7 +1338,8 @@ fn bound_type_enum() {
     struct Inner<T: Default>(T);
 }
 
-#[deny(private_in_public)]
+#[allow(dead_code)]
+#[deny(private_interfaces)]
 #[test]
 fn bound_struct_field() {
     #[derive(FromStr)]
@@ -1034,6 +1580,22 @@ fn new_struct_field_x2() {
     assert_from_str_err::<TestRange>("2-1");
 }
 
+#[test]
+fn new_struct_keyword_field() {
+    #[derive(FromStr, Debug, PartialEq)]
+    #[display("{fn}")]
+    #[from_str(new = Self::new(r#fn))]
+    struct TestStruct {
+        r#fn: u32,
+    }
+    impl TestStruct {
+        fn new(r#fn: u32) -> Self {
+            Self { r#fn: r#fn + 1 }
+        }
+    }
+    assert_from_str("5", TestStruct { r#fn: 6 });
+}
+
 #[test]
 fn new_enum() {
     #[derive(Display, FromStr, Debug, Eq, PartialEq)]
@@ -1068,6 +1630,99 @@ fn new_enum() {
     assert_from_str_err::<NonZeroEnum>("Y 0");
 }
 
+#[test]
+fn validate_struct() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{start}..{end}")]
+    #[from_str(validate = Self::validate)]
+    struct Range {
+        start: u32,
+        end: u32,
+    }
+    impl Range {
+        fn validate(&self) -> Result<(), String> {
+            if self.start <= self.end {
+                Ok(())
+            } else {
+                Err(format!("{} > {}", self.start, self.end))
+            }
+        }
+    }
+    assert_from_str("1..2", Range { start: 1, end: 2 });
+    assert_eq!(
+        "2..1".parse::<Range>(),
+        Err(ParseError::from_error("2 > 1"))
+    );
+    assert_eq!("2..1".parse::<Range>().unwrap_err().to_string(), "2 > 1");
+}
+
+#[test]
+fn validate_struct_default() {
+    #[derive(FromStr, Debug, Eq, PartialEq, Default)]
+    #[display("{a}")]
+    #[from_str(default, validate = validate_b)]
+    struct TestStruct {
+        a: u32,
+        b: u32,
+    }
+    fn validate_b(value: &TestStruct) -> Result<(), ParseError> {
+        if value.a != 0 {
+            Ok(())
+        } else {
+            Err(ParseError::with_message("zero"))
+        }
+    }
+    assert_from_str("1", TestStruct { a: 1, b: 0 });
+    assert_eq!(
+        "0".parse::<TestStruct>(),
+        Err(ParseError::with_message("zero"))
+    );
+}
+
+#[test]
+fn validate_struct_new() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{0}")]
+    #[from_str(new = Self::new(_0), validate = Self::validate)]
+    struct TestStruct(u32);
+    impl TestStruct {
+        fn new(value: u32) -> Self {
+            Self(value * 2)
+        }
+        fn validate(&self) -> Result<(), &'static str> {
+            if self.0 < 10 {
+                Ok(())
+            } else {
+                Err("too large")
+            }
+        }
+    }
+    assert_from_str("4", TestStruct(8));
+    assert_from_str_err::<TestStruct>("5");
+}
+
+#[test]
+fn validate_variant() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    enum TestEnum {
+        #[display("{0}")]
+        #[from_str(validate = Self::validate_small)]
+        Small(u32),
+        #[display("{0}")]
+        Large(u32),
+    }
+    impl TestEnum {
+        fn validate_small(&self) -> Result<(), &'static str> {
+            match *self {
+                Self::Small(x) if x < 10 => Ok(()),
+                _ => Err("not small"),
+            }
+        }
+    }
+    assert_from_str("5", TestEnum::Small(5));
+    assert_from_str("50", TestEnum::Large(50));
+}
+
 #[test]
 fn variant_ignore() {
     #[derive(Debug, Eq, PartialEq)]
@@ -1114,6 +1769,1066 @@ fn regex_capture_prefix_escape() {
     assert_from_str_err::<TestStruct>("aa");
 }
 
+#[test]
+fn format_arg_path() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}{Self::UNIT}")]
+    struct TestStruct {
+        a: u32,
+    }
+    impl TestStruct {
+        const UNIT: &'static str = "m";
+    }
+    assert_from_str("10m", TestStruct { a: 10 });
+    assert_from_str_err::<TestStruct>("10");
+    assert_from_str_err::<TestStruct>("10cm");
+}
+
+#[test]
+fn format_arg_named() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a} {unit:>3}", unit = Self::UNIT)]
+    struct TestStruct {
+        a: u32,
+    }
+    impl TestStruct {
+        const UNIT: &'static str = "m";
+    }
+    assert_from_str("10   m", TestStruct { a: 10 });
+    assert_from_str_err::<TestStruct>("10 m");
+}
+
+#[test]
+fn format_arg_escape() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}{sep}{b}", sep = ".*")]
+    struct TestStruct {
+        a: u32,
+        b: u32,
+    }
+    assert_from_str("1.*2", TestStruct { a: 1, b: 2 });
+    assert_from_str_err::<TestStruct>("1xx2");
+}
+
+#[test]
+fn format_arg_changed() {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    static UNIT: AtomicU32 = AtomicU32::new(1);
+
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}x{unit}", unit = UNIT.load(Ordering::Relaxed))]
+    struct TestStruct {
+        a: u32,
+    }
+    assert_from_str("5x1", TestStruct { a: 5 });
+    UNIT.store(2, Ordering::Relaxed);
+    assert_from_str("5x2", TestStruct { a: 5 });
+    assert_from_str_err::<TestStruct>("5x1");
+}
+
+#[test]
+fn format_arg_variant() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    enum TestEnum {
+        #[display("{0}{unit}", unit = Self::UNIT)]
+        A(u32),
+        #[display("{Self::UNIT}")]
+        B,
+    }
+    impl TestEnum {
+        const UNIT: &'static str = "kg";
+    }
+    assert_from_str("1kg", TestEnum::A(1));
+    assert_from_str("kg", TestEnum::B);
+    assert_from_str_err::<TestEnum>("1g");
+}
+
+#[test]
+fn format_arg_field() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a},{b}")]
+    struct TestStruct {
+        #[display("{}{unit}", unit = "cm")]
+        a: u32,
+        b: u32,
+    }
+    assert_from_str("5cm,6", TestStruct { a: 5, b: 6 });
+    assert_from_str_err::<TestStruct>("5,6");
+}
+
+#[test]
+fn format_spec_width_field() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{width}:[{a:>width$}]")]
+    struct TestStruct {
+        a: u32,
+        width: usize,
+    }
+    assert_from_str("3:[  5]", TestStruct { a: 5, width: 3 });
+}
+
+#[test]
+fn format_spec_width_string() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("[{a:>w$}][{b:*<w$}]", w = 4)]
+    struct TestStruct {
+        a: String,
+        b: String,
+    }
+    assert_from_str(
+        "[  ab][cd**]",
+        TestStruct {
+            a: "ab".into(),
+            b: "cd".into(),
+        },
+    );
+}
+
+#[test]
+fn format_spec_align() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("[{a:<4}][{b:>4}][{c:-^6}]")]
+    struct TestStruct {
+        a: String,
+        b: String,
+        c: String,
+    }
+    assert_from_str(
+        "[ab  ][  cd][--ef--]",
+        TestStruct {
+            a: "ab".into(),
+            b: "cd".into(),
+            c: "ef".into(),
+        },
+    );
+    assert_from_str(
+        "[ ab ][ cd ][-ef-]",
+        TestStruct {
+            a: " ab".into(),
+            b: "cd ".into(),
+            c: "ef".into(),
+        },
+    );
+}
+
+#[test]
+fn format_spec_zero() {
+    #[derive(FromStr, Debug, PartialEq)]
+    #[display("{a:05},{b:+08.2}")]
+    struct TestStruct {
+        a: i32,
+        b: f64,
+    }
+    assert_from_str("-0012,+0001.50", TestStruct { a: -12, b: 1.5 });
+}
+
+#[test]
+fn format_spec_radix() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a:x} {b:X} {c:o} {d:08b}")]
+    struct TestStruct {
+        a: u32,
+        b: u8,
+        c: u16,
+        d: u8,
+    }
+    assert_from_str(
+        "ff FE 17 00000101",
+        TestStruct {
+            a: 255,
+            b: 254,
+            c: 15,
+            d: 5,
+        },
+    );
+    assert_from_str_err::<TestStruct>("fg FE 17 00000101");
+}
+
+#[test]
+fn format_spec_radix_prefix() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a:#x} {b:#X} {c:#o} {d:#010b}")]
+    struct TestStruct {
+        a: u32,
+        b: u8,
+        c: u16,
+        d: u8,
+    }
+    assert_from_str(
+        "0xff 0xFE 0o17 0b00000101",
+        TestStruct {
+            a: 255,
+            b: 254,
+            c: 15,
+            d: 5,
+        },
+    );
+    assert_from_str_err::<TestStruct>("ff 0xFE 0o17 0b00000101");
+    assert_from_str_err::<TestStruct>("0x+ff 0xFE 0o17 0b00000101");
+}
+
+#[test]
+fn format_spec_radix_signed() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a:x},{b:#b}")]
+    struct TestStruct {
+        a: i8,
+        b: i32,
+    }
+    assert_from_str("ff,0b101", TestStruct { a: -1, b: 5 });
+    assert_from_str(
+        "80,0b11111111111111111111111111111110",
+        TestStruct { a: -128, b: -2 },
+    );
+}
+
+#[test]
+fn format_spec_radix_generic() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a:#x}")]
+    struct TestStruct<T> {
+        a: T,
+    }
+    assert_from_str("10", TestStruct { a: 10u64 });
+    assert_from_str_err::<TestStruct<u64>>("0x10");
+}
+
+#[test]
+fn format_spec_radix_custom_type() {
+    #[derive(Debug, Eq, PartialEq)]
+    struct Addr(u32);
+    impl core::fmt::LowerHex for Addr {
+        fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            core::fmt::LowerHex::fmt(&self.0, f)
+        }
+    }
+    impl FromStr for Addr {
+        type Err = core::num::ParseIntError;
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Ok(Self(u32::from_str_radix(s.trim_start_matches("0x"), 16)?))
+        }
+    }
+
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a:#x}")]
+    struct TestStruct {
+        a: Addr,
+    }
+    assert_from_str("0x1f", TestStruct { a: Addr(31) });
+}
+
+#[test]
+fn format_spec_radix_field_format() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    enum TestEnum {
+        #[display("A:{0}")]
+        A(#[display("{:x}")] u32),
+    }
+    assert_from_str("A:1f", TestEnum::A(31));
+}
+
+#[test]
+fn format_spec_exp() {
+    #[derive(FromStr, Debug, PartialEq)]
+    #[display("{a:e} {b:E} {c:e}")]
+    struct TestStruct {
+        a: u32,
+        b: f64,
+        c: i64,
+    }
+    assert_from_str(
+        "1.2e3 1.5E-1 -5e0",
+        TestStruct {
+            a: 1200,
+            b: 0.15,
+            c: -5,
+        },
+    );
+    assert_from_str_err::<TestStruct>("1.25e1 1.5E-1 -5e0");
+}
+
+#[test]
+fn format_spec_exp_huge() {
+    #[derive(FromStr, Debug, PartialEq)]
+    #[display("{a:e}")]
+    struct TestStruct {
+        a: u128,
+    }
+    assert_from_str(
+        "3.40282366920938463463374607431768211455e38",
+        TestStruct { a: u128::MAX },
+    );
+    assert_from_str_err::<TestStruct>("1e39");
+    assert_from_str_err::<TestStruct>("1e99999999999");
+}
+
+#[test]
+fn infer_regex_integer() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}{b}{c}")]
+    struct TestStruct {
+        a: i32,
+        b: u8,
+        c: String,
+    }
+    assert_from_str(
+        "-12+3abc",
+        TestStruct {
+            a: -12,
+            b: 3,
+            c: "abc".into(),
+        },
+    );
+}
+
+#[test]
+fn infer_regex_float() {
+    #[derive(FromStr, Debug, PartialEq)]
+    #[display("{a}{unit}")]
+    struct TestStruct {
+        a: f64,
+        unit: String,
+    }
+    assert_from_str(
+        "1.5e3km",
+        TestStruct {
+            a: 1500.0,
+            unit: "km".into(),
+        },
+    );
+    assert_from_str(
+        "-.5m",
+        TestStruct {
+            a: -0.5,
+            unit: "m".into(),
+        },
+    );
+}
+
+#[test]
+fn infer_regex_bool_char() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}{b}{c}")]
+    struct TestStruct {
+        a: bool,
+        b: char,
+        c: String,
+    }
+    assert_from_str(
+        "truexyz",
+        TestStruct {
+            a: true,
+            b: 'x',
+            c: "yz".into(),
+        },
+    );
+}
+
+#[test]
+fn infer_regex_radix() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a:#x}{b}")]
+    struct TestStruct {
+        a: u32,
+        b: String,
+    }
+    assert_from_str(
+        "0x1fzz",
+        TestStruct {
+            a: 31,
+            b: "zz".into(),
+        },
+    );
+}
+
+#[test]
+fn infer_regex_field_format() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}{b}")]
+    struct TestStruct {
+        #[display("[{}]")]
+        a: u32,
+        b: u32,
+    }
+    assert_from_str("[1]2", TestStruct { a: 1, b: 2 });
+}
+
+#[test]
+fn infer_regex_unit_enum() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display(style = "snake_case")]
+    enum Unit {
+        M,
+        Km,
+        #[display("m/s")]
+        MeterPerSecond,
+    }
+
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}{b}{c}")]
+    struct TestStruct {
+        a: Unit,
+        b: Unit,
+        c: String,
+    }
+    assert_from_str(
+        "kmm/sx",
+        TestStruct {
+            a: Unit::Km,
+            b: Unit::MeterPerSecond,
+            c: "x".into(),
+        },
+    );
+    assert_from_str_err::<TestStruct>("kmxm");
+}
+
+#[test]
+fn infer_regex_unit_enum_generic_struct() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    enum Unit {
+        A,
+        B,
+    }
+
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}{b}")]
+    struct TestStruct<T> {
+        a: Unit,
+        b: T,
+    }
+    assert_from_str(
+        "BAx",
+        TestStruct {
+            a: Unit::B,
+            b: "Ax".to_string(),
+        },
+    );
+}
+
+#[test]
+fn infer_regex_unit_enum_trim() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    enum Unit {
+        A,
+        B,
+    }
+
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}{b}")]
+    #[from_str(trim)]
+    struct TestStruct {
+        a: Unit,
+        b: String,
+    }
+    assert_from_str(
+        " A Bx",
+        TestStruct {
+            a: Unit::A,
+            b: "Bx".into(),
+        },
+    );
+}
+
+#[test]
+fn infer_regex_unit_enum_disabled() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    enum Unit {
+        A,
+        B,
+    }
+
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}{b}")]
+    #[from_str(infer_regex = false)]
+    struct TestStruct {
+        a: Unit,
+        b: Unit,
+    }
+    assert_from_str_err::<TestStruct>("AB");
+}
+
+#[test]
+fn infer_regex_disabled() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}{b}")]
+    #[from_str(infer_regex = false)]
+    struct TestStruct {
+        a: u32,
+        b: String,
+    }
+    assert_from_str_err::<TestStruct>("12abc");
+}
+
+#[test]
+fn infer_regex_disabled_field() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}{b}")]
+    struct TestStruct {
+        #[from_str(infer_regex = false)]
+        a: u32,
+        b: u32,
+    }
+    assert_from_str_err::<TestStruct>("12");
+}
+
+#[test]
+fn infer_regex_disabled_variant() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[from_str(infer_regex = false)]
+    enum TestEnum {
+        #[display("{0}{1}")]
+        A(u32, String),
+        #[display("{0}{1}")]
+        #[from_str(infer_regex = true)]
+        B(bool, String),
+    }
+    assert_from_str("truex", TestEnum::B(true, "x".into()));
+    assert_from_str_err::<TestEnum>("12x");
+}
+
+#[test]
+fn regex_preset_field() {
+    #[derive(FromStr, Debug, PartialEq)]
+    #[display("{a}{b}{c} {d}")]
+    #[from_str(infer_regex = false)]
+    struct TestStruct {
+        #[from_str(regex = @int)]
+        a: i32,
+        #[from_str(regex = @ident)]
+        b: String,
+        #[from_str(regex = @until_ws)]
+        c: String,
+        #[from_str(regex = @float)]
+        d: f64,
+    }
+    assert_from_str(
+        "-12abc_1+-x 1.5",
+        TestStruct {
+            a: -12,
+            b: "abc_1".into(),
+            c: "+-x".into(),
+            d: 1.5,
+        },
+    );
+}
+
+#[test]
+fn regex_preset_word_hex_quoted_uint() {
+    #[derive(FromStr, Debug, PartialEq)]
+    #[display("{a} {b:x}={c}{d}")]
+    struct TestStruct {
+        #[from_str(regex = @word)]
+        a: String,
+        #[from_str(regex = @hex)]
+        b: u32,
+        #[from_str(regex = @quoted)]
+        c: String,
+        #[from_str(regex = @uint)]
+        d: u8,
+    }
+    assert_from_str(
+        r#"ab_1 ff="x\"y"12"#,
+        TestStruct {
+            a: "ab_1".into(),
+            b: 255,
+            c: r#""x\"y""#.into(),
+            d: 12,
+        },
+    );
+}
+
+#[test]
+fn regex_preset_capture() {
+    #[derive(FromStr, Debug, PartialEq)]
+    #[from_str(regex = "(?<a:@int>)(?<b:@ident>)")]
+    struct TestStruct {
+        a: i32,
+        b: String,
+    }
+    assert_from_str(
+        "12ab",
+        TestStruct {
+            a: 12,
+            b: "ab".into(),
+        },
+    );
+}
+
+#[test]
+fn whitespace_flexible() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}, {b}")]
+    #[from_str(whitespace = "flexible")]
+    struct TestStruct {
+        a: u32,
+        b: u32,
+    }
+    assert_from_str("1, 2", TestStruct { a: 1, b: 2 });
+    assert_from_str("1,2", TestStruct { a: 1, b: 2 });
+    assert_from_str("1 ,  2", TestStruct { a: 1, b: 2 });
+    assert_from_str("1\t,\n2", TestStruct { a: 1, b: 2 });
+    assert_from_str_err::<TestStruct>("1;2");
+}
+
+#[test]
+fn whitespace_flexible_words() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a} to  {b}")]
+    #[from_str(whitespace = "flexible")]
+    struct TestStruct {
+        a: String,
+        b: String,
+    }
+    assert_from_str(
+        "x   to y",
+        TestStruct {
+            a: "x".into(),
+            b: "y".into(),
+        },
+    );
+    assert_from_str_err::<TestStruct>("x t o y");
+}
+
+#[test]
+fn whitespace_flexible_only_whitespace() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a} {b}")]
+    #[from_str(whitespace = "flexible")]
+    struct TestStruct {
+        a: u32,
+        b: u32,
+    }
+    assert_from_str("1   2", TestStruct { a: 1, b: 2 });
+    assert_from_str_err::<TestStruct>("12");
+}
+
+#[test]
+fn whitespace_variant() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[from_str(whitespace = "flexible")]
+    enum TestEnum {
+        #[display("{0} + {1}")]
+        Add(u32, u32),
+        #[display("{0} - {1}")]
+        #[from_str(whitespace = "exact")]
+        Sub(u32, u32),
+    }
+    assert_from_str("1+2", TestEnum::Add(1, 2));
+    assert_from_str("1 - 2", TestEnum::Sub(1, 2));
+    assert_from_str_err::<TestEnum>("1-2");
+}
+
+#[test]
+fn trim_field() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a},{b}")]
+    struct TestStruct {
+        #[from_str(trim)]
+        a: String,
+        b: String,
+    }
+    assert_from_str(
+        " x , y ",
+        TestStruct {
+            a: "x".into(),
+            b: " y ".into(),
+        },
+    );
+}
+
+#[test]
+fn trim_struct() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}={b}")]
+    #[from_str(trim)]
+    struct TestStruct {
+        a: String,
+        #[from_str(infer_regex = false)]
+        b: u32,
+    }
+    assert_from_str(
+        " key = 10 ",
+        TestStruct {
+            a: "key".into(),
+            b: 10,
+        },
+    );
+}
+
+#[test]
+fn trim_inferred_regex() {
+    #[derive(FromStr, Debug, PartialEq)]
+    #[display("{a},{b},{c},{d}")]
+    #[from_str(trim)]
+    struct TestStruct {
+        a: u32,
+        b: f64,
+        c: bool,
+        d: char,
+    }
+    assert_from_str(
+        " 1 , 2.5 ,true , x ",
+        TestStruct {
+            a: 1,
+            b: 2.5,
+            c: true,
+            d: 'x',
+        },
+    );
+    assert_from_str(
+        "1,2.5,true,x",
+        TestStruct {
+            a: 1,
+            b: 2.5,
+            c: true,
+            d: 'x',
+        },
+    );
+    assert_from_str_err::<TestStruct>(" 1 ,x,true,x");
+}
+
+#[test]
+fn trim_inferred_regex_field() {
+    #[derive(FromStr, Debug, PartialEq)]
+    #[display("{a},{b}")]
+    struct TestStruct {
+        #[from_str(trim)]
+        a: u32,
+        b: u32,
+    }
+    assert_from_str(" 1 ,2", TestStruct { a: 1, b: 2 });
+    assert_from_str_err::<TestStruct>("1, 2");
+}
+
+#[test]
+fn quote_field() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a},{b}")]
+    struct TestStruct {
+        #[display(quote)]
+        a: String,
+        b: String,
+    }
+    assert_from_str(
+        r#""a,\"b\"\\c",d"#,
+        TestStruct {
+            a: r#"a,"b"\c"#.into(),
+            b: "d".into(),
+        },
+    );
+    assert_from_str_err::<TestStruct>("a,b");
+    assert_from_str_err::<TestStruct>(r#""a"b",c"#);
+}
+
+#[test]
+fn quote_char() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a} {b}")]
+    struct TestStruct {
+        #[display(quote = "'")]
+        a: String,
+        #[display(quote = "|")]
+        b: u32,
+    }
+    assert_from_str(
+        r#"'it\'s' |12|"#,
+        TestStruct {
+            a: "it's".into(),
+            b: 12,
+        },
+    );
+}
+
+#[test]
+fn lazy_by_default() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}.{b}")]
+    struct TestStruct {
+        a: String,
+        b: String,
+    }
+    assert_from_str(
+        "1.2.3",
+        TestStruct {
+            a: "1".into(),
+            b: "2.3".into(),
+        },
+    );
+}
+
+#[test]
+fn greedy_field() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{stem}.{ext}")]
+    struct TestStruct {
+        #[from_str(greedy)]
+        stem: String,
+        ext: String,
+    }
+    assert_from_str(
+        "archive.tar.gz",
+        TestStruct {
+            stem: "archive.tar".into(),
+            ext: "gz".into(),
+        },
+    );
+}
+
+#[test]
+fn greedy_struct() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}.{b}.{c}")]
+    #[from_str(greedy)]
+    struct TestStruct {
+        a: String,
+        b: String,
+        c: String,
+    }
+    assert_from_str(
+        "1.2.3.4",
+        TestStruct {
+            a: "1.2".into(),
+            b: "3".into(),
+            c: "4".into(),
+        },
+    );
+}
+
+#[test]
+fn greedy_variant() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    enum TestEnum {
+        #[display("{0}-{1}")]
+        #[from_str(greedy)]
+        A(String, String),
+    }
+    assert_from_str("a-b-c", TestEnum::A("a-b".into(), "c".into()));
+}
+
+#[test]
+fn from_str_enum_same_regex_after_fallible_variant() {
+    #[derive(FromStr, Debug, PartialEq)]
+    enum TestEnum {
+        #[display("{0}")]
+        A(u32),
+        #[display("{0}")]
+        B(String),
+    }
+    assert_from_str("10", TestEnum::A(10));
+    assert_from_str("abc", TestEnum::B("abc".into()));
+}
+
+#[test]
+fn from_str_enum_same_regex_after_fallible_literal_variant() {
+    #[derive(FromStr, Debug, PartialEq)]
+    #[allow(dead_code)]
+    enum TestEnum {
+        #[display("x")]
+        #[from_str(new = None::<Self>)]
+        A,
+        #[from_str(regex = "x")]
+        B,
+    }
+    assert_from_str("x", TestEnum::B);
+}
+
+#[test]
+fn from_str_enum_priority() {
+    #[derive(FromStr, Debug, PartialEq)]
+    enum TestEnum {
+        #[display("{0}")]
+        Host(String),
+        #[display("{0}:{1}")]
+        #[from_str(priority = 1)]
+        HostPort(String, u16),
+    }
+    assert_from_str("a", TestEnum::Host("a".into()));
+    assert_from_str("a:80", TestEnum::HostPort("a".into(), 80));
+    assert_from_str("a:b", TestEnum::Host("a:b".into()));
+}
+
+#[test]
+fn from_str_enum_priority_over_literal() {
+    #[derive(FromStr, Debug, PartialEq)]
+    enum TestEnum {
+        #[display("zero")]
+        Zero,
+        #[display("{0}")]
+        #[from_str(regex = "(?<0>[0-9a-z]+)", priority = 1)]
+        Number(u32),
+    }
+    assert_from_str("0", TestEnum::Number(0));
+    assert_from_str("zero", TestEnum::Zero);
+}
+
+#[test]
+fn from_str_enum_match_longest() {
+    #[derive(FromStr, Debug, PartialEq)]
+    #[from_str(match = "longest")]
+    enum TestEnum {
+        #[display("{0}")]
+        Host(String),
+        #[display("{0}.local")]
+        Local(String),
+        #[display("{0}.example.com")]
+        Example(String),
+    }
+    assert_from_str("a", TestEnum::Host("a".into()));
+    assert_from_str("a.local", TestEnum::Local("a".into()));
+    assert_from_str("a.example.com", TestEnum::Example("a".into()));
+}
+
+#[test]
+fn from_str_enum_match_longest_with_priority() {
+    #[derive(FromStr, Debug, PartialEq)]
+    #[from_str(match = "longest")]
+    enum TestEnum {
+        #[display("{0}")]
+        #[from_str(regex = "(?<0>[a-z.]+)", priority = 1)]
+        Host(String),
+        #[display("{0}.local")]
+        Local(String),
+    }
+    assert_from_str("a.local", TestEnum::Host("a.local".into()));
+    assert_from_str("1.local", TestEnum::Local("1".into()));
+}
+
+#[test]
+fn from_str_hygiene_struct() {
+    #[derive(FromStr, Debug, PartialEq)]
+    #[display("{s},{c},{value},{RE},{re},{e},{m}")]
+    #[allow(non_snake_case)]
+    struct TestStruct {
+        s: u32,
+        c: u32,
+        value: u32,
+        RE: u32,
+        re: u32,
+        e: u32,
+        m: u32,
+    }
+    assert_from_str(
+        "1,2,3,4,5,6,7",
+        TestStruct {
+            s: 1,
+            c: 2,
+            value: 3,
+            RE: 4,
+            re: 5,
+            e: 6,
+            m: 7,
+        },
+    );
+}
+
+#[test]
+fn from_str_hygiene_new() {
+    #[derive(FromStr, Debug, PartialEq)]
+    #[display("{s},{c},{value},{RE},{re}")]
+    #[from_str(new = Self::new(s, c, value, RE, re))]
+    #[allow(non_snake_case)]
+    struct TestStruct {
+        s: u32,
+        c: u32,
+        value: u32,
+        RE: u32,
+        re: u32,
+    }
+    #[allow(non_snake_case)]
+    impl TestStruct {
+        fn new(s: u32, c: u32, value: u32, RE: u32, re: u32) -> Option<Self> {
+            Some(Self {
+                s: s + 10,
+                c,
+                value,
+                RE,
+                re,
+            })
+        }
+    }
+    assert_from_str(
+        "1,2,3,4,5",
+        TestStruct {
+            s: 11,
+            c: 2,
+            value: 3,
+            RE: 4,
+            re: 5,
+        },
+    );
+}
+
+#[test]
+fn from_str_hygiene_default() {
+    #[derive(FromStr, Debug, PartialEq, Default)]
+    #[display("{s},{c}")]
+    #[from_str(default)]
+    struct TestStruct {
+        s: u32,
+        c: u32,
+        #[from_str(default = s)]
+        value: u32,
+    }
+    assert_from_str(
+        "1,2",
+        TestStruct {
+            s: 1,
+            c: 2,
+            value: 1,
+        },
+    );
+}
+
+#[test]
+fn from_str_hygiene_field_chain() {
+    #[derive(Debug, PartialEq, Default)]
+    struct Inner {
+        s: u32,
+    }
+    #[derive(FromStr, Debug, PartialEq)]
+    #[display("{field_value.s}")]
+    struct TestStruct {
+        #[from_str(default)]
+        field_value: Inner,
+    }
+    assert_from_str(
+        "1",
+        TestStruct {
+            field_value: Inner { s: 1 },
+        },
+    );
+}
+
+#[test]
+fn from_str_hygiene_enum() {
+    #[derive(FromStr, Debug, PartialEq)]
+    enum TestEnum {
+        #[display("{s}-{parse_variant}")]
+        #[from_str(new = Self::new(s, parse_variant))]
+        A { s: u32, parse_variant: u32 },
+        #[display("{value}")]
+        B { value: String },
+    }
+    impl TestEnum {
+        fn new(s: u32, parse_variant: u32) -> Option<Self> {
+            (s != 0).then_some(Self::A { s, parse_variant })
+        }
+    }
+    assert_from_str(
+        "1-2",
+        TestEnum::A {
+            s: 1,
+            parse_variant: 2,
+        },
+    );
+    assert_from_str(
+        "0-2",
+        TestEnum::B {
+            value: "0-2".into(),
+        },
+    );
+}
+
 fn assert_from_str<T: FromStr + Debug + PartialEq>(s: &str, value: T)
 where
     <T as FromStr>::Err: Display,
@@ -0,0 +1,67 @@
+use parse_display::*;
+
+#[test]
+fn parse_positional() {
+    let value: Result<(u32, String, u16), ParseError> = parse!("{}-{}:{}", "1-abc:80");
+    assert_eq!(value, Ok((1, "abc".into(), 80)));
+}
+
+#[test]
+fn parse_positional_string() {
+    let input = String::from("10,20");
+    let (a, b): (u8, u8) = parse!("{},{}", input).unwrap();
+    assert_eq!((a, b), (10, 20));
+}
+
+#[test]
+fn parse_positional_index() {
+    let value: Result<(u32, u32), ParseError> = parse!("{1}<{0}", "1<2");
+    assert_eq!(value, Ok((2, 1)));
+}
+
+#[test]
+fn parse_positional_one() {
+    let value: Result<(u32,), ParseError> = parse!("[{}]", "[5]");
+    assert_eq!(value, Ok((5,)));
+}
+
+#[test]
+fn parse_positional_error() {
+    let value: Result<(u32, u32), ParseError> = parse!("{}-{}", "1-x");
+    assert!(value.is_err());
+    let value: Result<(u32, u32), ParseError> = parse!("{}-{}", "1+2");
+    assert!(value.is_err());
+}
+
+#[test]
+fn parse_format_spec() {
+    let value: Result<(u32, u32), ParseError> = parse!("{:x}/{:>4}", "ff/  12");
+    assert_eq!(value, Ok((255, 12)));
+}
+
+#[test]
+fn parse_named() {
+    let mut host = String::new();
+    let mut port = 0u16;
+    parse!("{host}:{port}", "localhost:8080").unwrap();
+    assert_eq!(host, "localhost");
+    assert_eq!(port, 8080);
+}
+
+#[test]
+fn parse_named_error() {
+    let mut host = String::new();
+    let mut port = 0u16;
+    assert!(parse!("{host}:{port}", "localhost:x").is_err());
+    assert_eq!(host, "");
+    assert_eq!(port, 0);
+}
+
+#[test]
+fn parse_named_hygiene() {
+    let mut s = 0u32;
+    let mut c = 0u32;
+    let mut value = 0u32;
+    parse!("{s},{c},{value}", "1,2,3").unwrap();
+    assert_eq!((s, c, value), (1, 2, 3));
+}
@@ -0,0 +1,119 @@
+use crate::{FieldsByName, ParseError};
+use core::fmt::{Display, Error, Formatter, Result};
+use core::marker::PhantomData;
+use regex::Regex;
+
+/// A format specified at runtime.
+///
+/// See [`FieldsByName`] for details.
+pub struct Format<T> {
+    parts: Vec<FormatPart>,
+    fields: Vec<String>,
+    regex: Regex,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+enum FormatPart {
+    Str(String),
+    Field(String),
+}
+
+impl<T: FieldsByName> Format<T> {
+    /// Parse a format such as `"{a}-{b}"`.
+    ///
+    /// `{{` and `}}` are escaped brackets.
+    /// Returns an error if the format is invalid or contains a field that `T` does not have.
+    pub fn new(format: &str) -> core::result::Result<Self, ParseError> {
+        let mut parts = Vec::new();
+        let mut fields = Vec::new();
+        let mut regex = String::from("^");
+        let mut text = String::new();
+        let mut chars = format.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.as_str().starts_with('{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.as_str().starts_with('}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let rest = chars.as_str();
+                    let end = match rest.find('}') {
+                        Some(end) => end,
+                        None => return Err(ParseError::with_message("invalid format.")),
+                    };
+                    let name = &rest[..end];
+                    if !T::FIELD_NAMES.contains(&name) {
+                        return Err(ParseError::from_error(format_args!(
+                            "unknown field `{name}`."
+                        )));
+                    }
+                    if !text.is_empty() {
+                        regex.push_str(&regex::escape(&text));
+                        parts.push(FormatPart::Str(std::mem::take(&mut text)));
+                    }
+                    regex.push_str("(?s:(.*?))");
+                    parts.push(FormatPart::Field(name.into()));
+                    fields.push(name.into());
+                    chars = rest[end + 1..].chars();
+                }
+                '}' => return Err(ParseError::with_message("invalid format.")),
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            regex.push_str(&regex::escape(&text));
+            parts.push(FormatPart::Str(text));
+        }
+        regex.push('$');
+        Ok(Self {
+            parts,
+            fields,
+            regex: Regex::new(&regex).unwrap(),
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Returns an object that implements [`Display`] for `value` by this format.
+    pub fn display<'a>(&'a self, value: &'a T) -> FormatDisplay<'a, T> {
+        FormatDisplay {
+            format: self,
+            value,
+        }
+    }
+
+    /// Parse `s` by this format.
+    ///
+    /// If the same field appears more than once, the first one is used.
+    pub fn parse(&self, s: &str) -> core::result::Result<T, ParseError> {
+        let c = match self.regex.captures(s) {
+            Some(c) => c,
+            None => return Err(ParseError::new()),
+        };
+        T::from_fields(&|name| {
+            let index = self.fields.iter().position(|field| field == name)?;
+            Some(c.get(index + 1)?.as_str())
+        })
+    }
+}
+
+/// The return value of [`Format::display`].
+pub struct FormatDisplay<'a, T> {
+    format: &'a Format<T>,
+    value: &'a T,
+}
+
+impl<T: FieldsByName> Display for FormatDisplay<'_, T> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        for part in &self.format.parts {
+            match part {
+                FormatPart::Str(s) => f.write_str(s)?,
+                FormatPart::Field(name) => self.value.fmt_field(name, f).unwrap_or(Err(Error))?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -43,14 +43,27 @@
 //! | attribute                                                     | struct | enum | variant | field |
 //! | ------------------------------------------------------------- | ------ | ---- | ------- | ----- |
 //! | [`#[display("...")]`](#display)                               | ✔      | ✔    | ✔       | ✔     |
-//! | [`#[display(style = "...")]`](#displaystyle--)                |        | ✔    | ✔       |       |
+//! | [`#[display(style = "...")]`](#displaystyle--)                | ✔      | ✔    | ✔       |       |
+//! | [`#[display(digits = "...")]`](#word-splitting)               | ✔      | ✔    | ✔       |       |
+//! | [`#[display(discriminant)]`](#displaydiscriminant)            |        | ✔    |         |       |
+//! | [`#[display(kv(...))]`](#displaykv)                           | ✔      |      |         |       |
+//! | [`#[display(quote)]`](#displayquote)                          |        |      |         | ✔     |
 //! | [`#[display(crate = ...)]`](#displaycrate--)                  | ✔      | ✔    |         |       |
 //! | [`#[display(bound(...))]`](#displaybound)                     | ✔      | ✔    | ✔       | ✔     |
 //! | [`#[from_str(bound(...))]`](#from_strbound)                   | ✔      | ✔    | ✔       | ✔     |
 //! | [`#[from_str(regex = "...")]`](#from_strregex--)              | ✔      | ✔    | ✔       | ✔     |
+//! | [`#[from_str(any_order)]`](#from_strany_order)                | ✔      |      | ✔       |       |
+//! | [`#[from_str(infer_regex = ...)]`](#from_strinfer_regex--)    | ✔      | ✔    | ✔       | ✔     |
+//! | [`#[from_str(whitespace = "...")]`](#from_strwhitespace--)    | ✔      | ✔    | ✔       |       |
+//! | [`#[from_str(trim)]`](#from_strtrim)                          | ✔      | ✔    | ✔       | ✔     |
+//! | [`#[from_str(greedy)]`](#from_strgreedy)                      | ✔      | ✔    | ✔       | ✔     |
 //! | [`#[from_str(new = ...)]`](#from_strnew--)                    | ✔      |      | ✔       |       |
+//! | [`#[from_str(validate = ...)]`](#from_strvalidate--)          | ✔      |      | ✔       |       |
 //! | [`#[from_str(ignore)]`](#from_strignore)                      |        |      | ✔       |       |
+//! | [`#[from_str(priority = ...)]`](#from_strpriority--)          |        |      | ✔       |       |
+//! | [`#[from_str(match = "...")]`](#from_strmatch--)              |        | ✔    |         |       |
 //! | [`#[from_str(default)]`](#from_strdefault)                    | ✔      |      |         | ✔     |
+//! | [`#[from_str(default = ...)]`](#from_strdefault--)            | ✔      |      |         | ✔     |
 //! | [`#[from_str(default_fields(...))]`](#from_strdefault_fields) | ✔      | ✔    | ✔       |       |
 //!
 //! `#[derive(Display)]` use `#[display]`.
@@ -185,6 +198,16 @@
 //! assert_eq!("VarB".parse(), Ok(MyEnum::VarB));
 //! ```
 //!
+//! ### Variant order
+//!
+//! When parsing enum, variants whose format contains no field are compared with the input first.
+//! Then, the other variants are tried in declaration order, and the first variant that is parsed successfully is used.
+//! This order can be changed by [`#[from_str(priority = ...)]`](#from_strpriority--) and [`#[from_str(match = "...")]`](#from_strmatch--).
+//!
+//! Two variants with the same format without field cause a compile error.
+//! A variant that can never be parsed because every input it matches is always parsed as other variants causes a warning.
+//! (The warning is reported by the `deprecated` lint, since derive macros cannot report warnings by themselves.)
+//!
 //! ### Field format
 //!
 //! You can specify the format of the field.
@@ -259,9 +282,131 @@
 //! assert_eq!(WithFormatParameter { a:5 }.to_string(), "0005");
 //! ```
 //!
+//! Width and precision can refer to other fields, such as `{value:.prec$}` or `{0:>1$}`.
+//!
+//! `FromStr` also uses the format parameter.
+//!
+//! - If width is specified, fill characters are trimmed on the side where the value is padded according to the alignment.
+//!   If no alignment is specified, they are trimmed on both sides.
+//! - If the format type is `x`, `X`, `o` or `b`, the value is parsed in the corresponding radix.
+//!   With `#`, the prefix `0x`, `0o` or `0b` is required.
+//!   Signed integers are parsed as two's complement, as they are displayed.
+//! - If the format type is `e` or `E`, the value is parsed in exponent notation.
+//!   For integers, the value must be an integer after the exponent is applied.
+//!
+//! Format types are used only for fields of primitive integer types (and floating point types for `e` and `E`).
+//! Fields of other types, including generic types, are parsed by `FromStr` as is.
+//!
+//! ```rust
+//! use parse_display::{Display, FromStr};
+//!
+//! #[derive(Display, FromStr, PartialEq, Debug)]
+//! #[display("{width}:{name:>width$}")]
+//! struct Padded {
+//!   name: String,
+//!   width: usize,
+//! }
+//! assert_eq!(Padded { name: "ab".into(), width: 4 }.to_string(), "4:  ab");
+//! assert_eq!("4:  ab".parse(), Ok(Padded { name: "ab".into(), width: 4 }));
+//! ```
+//!
+//! ```rust
+//! use parse_display::{Display, FromStr};
+//!
+//! #[derive(Display, FromStr, PartialEq, Debug)]
+//! #[display("{addr:#06x} {mask:08b} {value:e}")]
+//! struct Register {
+//!   addr: u16,
+//!   mask: u8,
+//!   value: u32,
+//! }
+//! let r = Register { addr: 0xff, mask: 5, value: 1200 };
+//! assert_eq!(r.to_string(), "0x00ff 00000101 1.2e3");
+//! assert_eq!("0x00ff 00000101 1.2e3".parse(), Ok(r));
+//! ```
+//!
+//! ### Conditional format
+//!
+//! `{?field:text}` outputs `text` only if the `bool` field is `true`. `text` cannot be empty.
+//! With `FromStr`, the field is set to `true` if `text` is present in the input, and `false` otherwise.
+//!
+//! ```rust
+//! use parse_display::{Display, FromStr};
+//!
+//! #[derive(Display, FromStr, PartialEq, Debug)]
+//! #[display("{name}{?admin: (admin)}")]
+//! struct User {
+//!   name: String,
+//!   admin: bool,
+//! }
+//! assert_eq!(User { name: "alice".into(), admin: true }.to_string(), "alice (admin)");
+//! assert_eq!(User { name: "bob".into(), admin: false }.to_string(), "bob");
+//! assert_eq!("alice (admin)".parse(), Ok(User { name: "alice".into(), admin: true }));
+//! assert_eq!("bob".parse(), Ok(User { name: "bob".into(), admin: false }));
+//! ```
+//!
+//! In field format, `{?:text}` means the field itself.
+//!
+//! ### Field name
+//!
+//! `{#field}` outputs the name of the field, converted by the style specified by [`#[display(style = "...")]`](#displaystyle--).
+//!
+//! With `FromStr`, the field name is treated as a literal text.
+//!
+//! ```rust
+//! use parse_display::{Display, FromStr};
+//!
+//! #[derive(Display, FromStr, PartialEq, Debug)]
+//! #[display("{#max_width}={max_width} {#max_height}={max_height}", style = "kebab-case")]
+//! struct Limits {
+//!   max_width: u32,
+//!   max_height: u32,
+//! }
+//! assert_eq!(Limits { max_width: 10, max_height: 20 }.to_string(), "max-width=10 max-height=20");
+//! assert_eq!("max-width=10 max-height=20".parse(), Ok(Limits { max_width: 10, max_height: 20 }));
+//! ```
+//!
+//! ### Format arguments
+//!
+//! A path containing `::` such as `{Self::UNIT}` outputs the value of the constant.
+//! Named arguments such as `#[display("{a}{unit}", unit = Self::UNIT)]` can be bound to any expression.
+//!
+//! With `FromStr`, the value is treated as a literal text.
+//! Since the text is known only at runtime, the regex is built each time `from_str` is called.
+//!
+//! ```rust
+//! use parse_display::{Display, FromStr};
+//!
+//! #[derive(Display, FromStr, PartialEq, Debug)]
+//! #[display("{value}{Self::UNIT}")]
+//! struct Length {
+//!   value: u32,
+//! }
+//! impl Length {
+//!   const UNIT: &'static str = "cm";
+//! }
+//! assert_eq!(Length { value: 10 }.to_string(), "10cm");
+//! assert_eq!("10cm".parse(), Ok(Length { value: 10 }));
+//!
+//! #[derive(Display, FromStr, PartialEq, Debug)]
+//! #[display("{value} {unit}", unit = Self::UNIT)]
+//! struct Weight {
+//!   value: u32,
+//! }
+//! impl Weight {
+//!   const UNIT: &'static str = "kg";
+//! }
+//! assert_eq!(Weight { value: 5 }.to_string(), "5 kg");
+//! assert_eq!("5 kg".parse(), Ok(Weight { value: 5 }));
+//! ```
+//!
+//! If a named argument has the same name as a field, the named argument is used.
+//! Named arguments that are not used in the format are errors.
+//!
 //! ## `#[display(style = "...")]`
 //!
 //! By writing `#[display(style = "...")]`, you can specify the variant name style.
+//! The style is also applied to field names output by [`{#field}`](#field-name).
 //! The following styles are available.
 //!
 //! - `none`
@@ -272,11 +417,15 @@
 //! - `camelCase`
 //! - `CamelCase`
 //! - `kebab-case`
-//! - `KEBAB-CASE`
+//! - `KEBAB-CASE` (or `COBOL-CASE`)
 //! - `Title Case`
 //! - `Title case`
 //! - `title case`
 //! - `TITLE CASE`
+//! - `dot.case`
+//! - `Train-Case`
+//! - `path/case`
+//! - `Ada_Case`
 //!
 //! ```rust
 //! use parse_display::{Display, FromStr};
@@ -322,6 +471,14 @@
 //!   VarL,
 //!   #[display(style = "TITLE CASE")]
 //!   VarM,
+//!   #[display(style = "dot.case")]
+//!   VarN,
+//!   #[display(style = "Train-Case")]
+//!   VarO,
+//!   #[display(style = "path/case")]
+//!   VarP,
+//!   #[display(style = "Ada_Case")]
+//!   VarQ,
 //! }
 //! assert_eq!(StyleExample::VarA1.to_string(), "VarA1");
 //! assert_eq!(StyleExample::varA2.to_string(), "varA2");
@@ -338,6 +495,133 @@
 //! assert_eq!(StyleExample::VarK.to_string(), "Var k");
 //! assert_eq!(StyleExample::VarL.to_string(), "var l");
 //! assert_eq!(StyleExample::VarM.to_string(), "VAR M");
+//! assert_eq!(StyleExample::VarN.to_string(), "var.n");
+//! assert_eq!(StyleExample::VarO.to_string(), "Var-O");
+//! assert_eq!(StyleExample::VarP.to_string(), "var/p");
+//! assert_eq!(StyleExample::VarQ.to_string(), "Var_Q");
+//! ```
+//!
+//! ### Word splitting
+//!
+//! Variant names are split into words by the following rules.
+//!
+//! - Characters other than letters and digits (e.g. `_`) separate words and are removed.
+//! - An uppercase letter following a lowercase letter starts a new word. (`VarA` → `Var`, `A`)
+//! - In a run of uppercase letters, the last one starts a new word if a lowercase letter follows it. (`HTTPServer` → `HTTP`, `Server`)
+//!
+//! Letters are classified and converted with the Unicode case mapping, so non-ASCII variant names such as `ÉtatInitial` become `état_initial`.
+//!
+//! How digits split words can be specified by `#[display(digits = "...")]`.
+//!
+//! | `digits`           | description                               | `Var2D`   | `Var2d`   |
+//! | ------------------ | ----------------------------------------- | --------- | --------- |
+//! | `suffix` (default) | Digits are the end of the preceding word. | `var2_d`  | `var2d`   |
+//! | `prefix`           | Digits are the start of a new word.       | `var_2d`  | `var_2d`  |
+//! | `separate`         | Digits are a word of their own.           | `var_2_d` | `var_2_d` |
+//!
+//! ```rust
+//! use parse_display::{Display, FromStr};
+//!
+//! #[derive(Display, FromStr, PartialEq, Debug)]
+//! #[display(style = "snake_case", digits = "prefix")]
+//! enum MyEnum {
+//!   HTTPServer,
+//!   Var2D,
+//! }
+//! assert_eq!(MyEnum::HTTPServer.to_string(), "http_server");
+//! assert_eq!(MyEnum::Var2D.to_string(), "var_2d");
+//! assert_eq!("var_2d".parse(), Ok(MyEnum::Var2D));
+//! ```
+//!
+//! ## `#[display(kv(...))]`
+//!
+//! By writing `#[display(kv)]` instead of a format, all fields are output as `name=value` separated by a space.
+//! The separator and the assignment text can be specified by `#[display(kv(sep = "...", assign = "..."))]`.
+//! Field names are converted by the style specified by [`#[display(style = "...")]`](#displaystyle--).
+//!
+//! With `FromStr`, the fields can appear in any order.
+//! Missing fields are set by [`#[from_str(default)]`](#from_strdefault) or [`#[from_str(default = ...)]`](#from_strdefault--), and are errors otherwise.
+//! Since values may contain the separator, an unknown key following a field whose value can contain the separator is parsed as a part of that value.
+//!
+//! ```rust
+//! use parse_display::{Display, FromStr};
+//!
+//! #[derive(Display, FromStr, PartialEq, Debug)]
+//! #[display(kv)]
+//! struct Record {
+//!   level: u32,
+//!   #[from_str(default)]
+//!   msg: String,
+//! }
+//! assert_eq!(Record { level: 1, msg: "start".into() }.to_string(), "level=1 msg=start");
+//! assert_eq!("msg=start level=1".parse(), Ok(Record { level: 1, msg: "start".into() }));
+//! assert_eq!("level=2".parse(), Ok(Record { level: 2, msg: "".into() }));
+//!
+//! #[derive(Display, FromStr, PartialEq, Debug)]
+//! #[display(kv(sep = ", ", assign = ": "), style = "kebab-case")]
+//! struct Size {
+//!   max_width: u32,
+//!   max_height: u32,
+//! }
+//! assert_eq!(Size { max_width: 1, max_height: 2 }.to_string(), "max-width: 1, max-height: 2");
+//! assert_eq!("max-height: 2, max-width: 1".parse(), Ok(Size { max_width: 1, max_height: 2 }));
+//! ```
+//!
+//! ## `#[display(quote)]`
+//!
+//! By writing `#[display(quote)]` on a field, the field is enclosed in `"`, and `"` and `\` in the field are escaped by `\`.
+//! The quote character can be specified by `#[display(quote = "'")]`.
+//!
+//! With `FromStr`, the quoted string is matched and unescaped before parsing the field,
+//! so any string can be displayed and parsed back even if it contains a separator.
+//!
+//! ```rust
+//! use parse_display::{Display, FromStr};
+//!
+//! #[derive(Display, FromStr, PartialEq, Debug)]
+//! #[display("{name},{value}")]
+//! struct MyStruct {
+//!   #[display(quote)]
+//!   name: String,
+//!   value: u32,
+//! }
+//! let value = MyStruct { name: r#"a,"b""#.into(), value: 1 };
+//! assert_eq!(value.to_string(), r#""a,\"b\"",1"#);
+//! assert_eq!(r#""a,\"b\"",1"#.parse(), Ok(value));
+//! ```
+//!
+//! ## `#[display(discriminant)]`
+//!
+//! By writing `#[display(discriminant)]` to a fieldless enum, the discriminant of each variant is used instead of the variant name.
+//!
+//! ```rust
+//! use parse_display::{Display, FromStr};
+//!
+//! #[derive(Display, FromStr, PartialEq, Debug)]
+//! #[display(discriminant)]
+//! enum Status {
+//!   Ok = 200,
+//!   NotFound = 404,
+//! }
+//! assert_eq!(Status::NotFound.to_string(), "404");
+//! assert_eq!("404".parse(), Ok(Status::NotFound));
+//! ```
+//!
+//! In the format, `{discriminant}` means the discriminant of the variant.
+//! The type of the discriminant is the integer type specified by `#[repr(...)]`, or `isize` if not specified.
+//!
+//! ```rust
+//! use parse_display::{Display, FromStr};
+//!
+//! #[derive(Display, FromStr, PartialEq, Debug)]
+//! #[display("{}:{discriminant}", discriminant)]
+//! #[repr(u8)]
+//! enum Register {
+//!   Status = 0x10,
+//!   Control,
+//! }
+//! assert_eq!(Register::Control.to_string(), "Control:17");
+//! assert_eq!("Control:17".parse(), Ok(Register::Control));
 //! ```
 //!
 //! ## `#[display(crate = ...)]`
@@ -499,8 +783,9 @@
 //! assert_eq!("10__20".parse(), Ok(MyStruct { a:10, b:20 }));
 //! ```
 //!
-//! If `#[from_str(regex = "...")]` is not set to field ,
-//! it operates in the same way as when `#[from_str(regex = "(?s:.*?)")]` is set.
+//! If `#[from_str(regex = "...")]` is not set to field,
+//! the regex is [inferred from the field type](#from_strinfer_regex--),
+//! and for other types it operates in the same way as when `#[from_str(regex = "(?s:.*?)")]` is set.
 //!
 //! ```rust
 //! use parse_display::FromStr;
@@ -556,6 +841,229 @@
 //!
 //! When using "field chain", you need to use [`#[from_str(default)]`](#from_strdefault).
 //!
+//! ### Regex presets
+//!
+//! The following presets can be used instead of regex.
+//!
+//! | preset      | regex                      | description                            |
+//! | ----------- | -------------------------- | -------------------------------------- |
+//! | `@int`      | `[+-]?[0-9]+`              | signed integer                         |
+//! | `@uint`     | `\+?[0-9]+`                | unsigned integer                       |
+//! | `@float`    |                            | decimal, exponent, `inf` and `NaN`     |
+//! | `@ident`    | `[A-Za-z_][0-9A-Za-z_]*`   | identifier                             |
+//! | `@word`     | `\w+`                      | word characters                        |
+//! | `@hex`      | `[0-9a-fA-F]+`             | hexadecimal digits                     |
+//! | `@quoted`   | `"(?:[^"\\]\|\\.)*"`          | double quoted string, including quotes |
+//! | `@until_ws` | `\S+`                      | characters until whitespace            |
+//!
+//! A preset can be specified for field as `#[from_str(regex = @int)]`,
+//! and for capture in regex as `(?<a:@int>)`.
+//!
+//! `@hex` only matches digits. To parse the digits as hexadecimal, use [`{:x}`](#format-parameter) in the format.
+//!
+//! ```rust
+//! use parse_display::FromStr;
+//!
+//! #[derive(FromStr, PartialEq, Debug)]
+//! #[display("{name} {value}")]
+//! struct MyStruct {
+//!   #[from_str(regex = @ident)]
+//!   name: String,
+//!   #[from_str(regex = @until_ws)]
+//!   value: String,
+//! }
+//! assert_eq!("abc x=1".parse(), Ok(MyStruct { name: "abc".into(), value: "x=1".into() }));
+//! assert!("abc x=1 y=2".parse::<MyStruct>().is_err());
+//!
+//! #[derive(FromStr, PartialEq, Debug)]
+//! #[from_str(regex = "(?<a:@int>),(?<b:@quoted>)")]
+//! struct MyStruct2 {
+//!   a: i32,
+//!   b: String,
+//! }
+//! assert_eq!(r#"-1,"x""#.parse(), Ok(MyStruct2 { a: -1, b: r#""x""#.into() }));
+//! ```
+//!
+//! ## `#[from_str(infer_regex = ...)]`
+//!
+//! Fields in a format are matched by a regex inferred from the field type.
+//!
+//! | type                             | regex                                |
+//! | -------------------------------- | ------------------------------------ |
+//! | `i8`, `i16`, ... `isize`         | `[+-]?[0-9]+`                        |
+//! | `u8`, `u16`, ... `usize`         | `\+?[0-9]+`                          |
+//! | `f32`, `f64`                     | decimal, exponent, `inf` and `NaN`   |
+//! | `bool`                           | `true\|false`                        |
+//! | `char`                           | a single character                   |
+//! | integers with `x`, `X`, `o`, `b` | digits of the radix                  |
+//! | enums deriving `FromStr`         | the text of each variant             |
+//!
+//! An enum gets a regex only if all its variants are parsed from fixed text, such as unit variants without format arguments.
+//! The enum type must be written without generic arguments.
+//!
+//! Fields of other types match any text, as few characters as possible.
+//! This allows formats without separators between fields.
+//!
+//! ```rust
+//! use parse_display::FromStr;
+//!
+//! #[derive(FromStr, PartialEq, Debug)]
+//! #[display("{value}{unit}")]
+//! struct Length {
+//!   value: f64,
+//!   unit: String,
+//! }
+//! assert_eq!("1.5km".parse(), Ok(Length { value: 1.5, unit: "km".into() }));
+//!
+//! #[derive(FromStr, PartialEq, Debug)]
+//! #[display(style = "lowercase")]
+//! enum Unit {
+//!   M,
+//!   Km,
+//! }
+//!
+//! #[derive(FromStr, PartialEq, Debug)]
+//! #[display("{unit}{note}")]
+//! struct Note {
+//!   unit: Unit,
+//!   note: String,
+//! }
+//! assert_eq!("kmmax".parse(), Ok(Note { unit: Unit::Km, note: "max".into() }));
+//! ```
+//!
+//! `#[from_str(infer_regex = false)]` disables the inference, and the field matches any text.
+//! When specified for struct, enum or variant, it applies to all fields, unless specified for the field.
+//!
+//! ## `#[from_str(whitespace = "...")]`
+//!
+//! Specifies how whitespace in the format is matched.
+//!
+//! | value        | description                                                                                    |
+//! | ------------ | ---------------------------------------------------------------------------------------------- |
+//! | `"exact"`    | Whitespace matches itself. (default)                                                           |
+//! | `"flexible"` | Whitespace at both ends of the text is optional, and whitespace in the text is one or more whitespace characters. |
+//!
+//! With `"flexible"`, text consisting only of whitespace matches one or more whitespace characters.
+//! Whitespace in variant names and field names is always matched exactly.
+//!
+//! ```rust
+//! use parse_display::FromStr;
+//!
+//! #[derive(FromStr, PartialEq, Debug)]
+//! #[display("{a}, {b}")]
+//! #[from_str(whitespace = "flexible")]
+//! struct MyStruct {
+//!   a: u32,
+//!   b: u32,
+//! }
+//! assert_eq!("1, 2".parse(), Ok(MyStruct { a: 1, b: 2 }));
+//! assert_eq!("1,2".parse(), Ok(MyStruct { a: 1, b: 2 }));
+//! assert_eq!("1 ,  2".parse(), Ok(MyStruct { a: 1, b: 2 }));
+//! ```
+//!
+//! ## `#[from_str(trim)]`
+//!
+//! Trims whitespace at both ends of the captured text before parsing the field.
+//! When specified for struct, enum or variant, it applies to all fields.
+//!
+//! ```rust
+//! use parse_display::FromStr;
+//!
+//! #[derive(FromStr, PartialEq, Debug)]
+//! #[display("{key}={value}")]
+//! #[from_str(trim)]
+//! struct MyStruct {
+//!   key: String,
+//!   value: String,
+//! }
+//! assert_eq!(" a = b ".parse(), Ok(MyStruct { key: "a".into(), value: "b".into() }));
+//! ```
+//!
+//! ## `#[from_str(greedy)]`
+//!
+//! Fields that match any text match as few characters as possible by default.
+//! By writing `#[from_str(greedy)]`, they match as many characters as possible.
+//! When specified for struct, enum or variant, it applies to all fields.
+//!
+//! When the input can be split in more than one way, the fields are matched from the first field in the format,
+//! and each field takes as many characters as possible if greedy, or as few characters as possible otherwise.
+//! So, a separator that appears in the input more than once is absorbed by the last field by default,
+//! and by the first greedy field if any.
+//! Fields whose regex is [inferred from the type](#from_strinfer_regex--) always match as many characters as possible.
+//!
+//! ```rust
+//! use parse_display::FromStr;
+//!
+//! #[derive(FromStr, PartialEq, Debug)]
+//! #[display("{stem}.{ext}")]
+//! struct FileName {
+//!   #[from_str(greedy)]
+//!   stem: String,
+//!   ext: String,
+//! }
+//! assert_eq!("a.tar.gz".parse(), Ok(FileName { stem: "a.tar".into(), ext: "gz".into() }));
+//!
+//! #[derive(FromStr, PartialEq, Debug)]
+//! #[display("{stem}.{ext}")]
+//! struct FileNameLazy {
+//!   stem: String,
+//!   ext: String,
+//! }
+//! assert_eq!("a.tar.gz".parse(), Ok(FileNameLazy { stem: "a".into(), ext: "tar.gz".into() }));
+//! ```
+//!
+//! ## `#[from_str(any_order)]`
+//!
+//! By writing `#[from_str(any_order)]`, the format is split into segments by a space, and the segments can appear in any order.
+//! The separator can be specified by `#[from_str(any_order(sep = "..."))]`.
+//! Each segment must start with fixed text that is not the start of another segment (e.g. `a=` and `b=`), so that segments can be identified.
+//!
+//! Missing fields are set by [`#[from_str(default)]`](#from_strdefault) or [`#[from_str(default = ...)]`](#from_strdefault--), and are errors otherwise.
+//! Segments without fields cannot be omitted, and each segment cannot appear more than once.
+//! This attribute cannot be used with [`#[from_str(regex = "...")]`](#from_strregex--).
+//!
+//! ```rust
+//! use parse_display::FromStr;
+//!
+//! #[derive(FromStr, PartialEq, Debug)]
+//! #[display("a={a} b={b}")]
+//! #[from_str(any_order)]
+//! struct MyStruct {
+//!   a: u32,
+//!   b: u32,
+//! }
+//! assert_eq!("a=1 b=2".parse(), Ok(MyStruct { a: 1, b: 2 }));
+//! assert_eq!("b=2 a=1".parse(), Ok(MyStruct { a: 1, b: 2 }));
+//!
+//! #[derive(FromStr, PartialEq, Debug)]
+//! #[display("<{a}>, [{b}]")]
+//! #[from_str(any_order(sep = ", "))]
+//! struct MyStruct2 {
+//!   a: u32,
+//!   b: u32,
+//! }
+//! assert_eq!("[2], <1>".parse(), Ok(MyStruct2 { a: 1, b: 2 }));
+//! ```
+//!
+//! A field value containing the separator followed by the start of a segment (e.g. `x a=1` with `a=`) is ambiguous,
+//! since it can be read as the next segment.
+//! Such text is parsed in the way the regex matches it, and may be rejected as a duplicate segment or read as part of another field.
+//! Use [`#[display(quote)]`](#displayquote) for fields whose values may contain such text.
+//!
+//! ```rust
+//! use parse_display::FromStr;
+//!
+//! #[derive(FromStr, PartialEq, Debug)]
+//! #[display("a={a} b={b}")]
+//! #[from_str(any_order)]
+//! struct MyStruct {
+//!   #[display(quote)]
+//!   a: String,
+//!   b: u32,
+//! }
+//! assert_eq!(r#"a="x b=1" b=2"#.parse(), Ok(MyStruct { a: "x b=1".into(), b: 2 }));
+//! ```
+//!
 //! ## `#[from_str(new = ...)]`
 //!
 //! If `#[from_str(new = ...)]` is specified, the value will be initialized with the specified expression instead of the constructor.
@@ -608,6 +1116,39 @@
 //! assert_eq!("0".parse::<MyNonZeroUSize>().is_err(), true);
 //! ```
 //!
+//! ## `#[from_str(validate = ...)]`
+//!
+//! If `#[from_str(validate = ...)]` is specified, the specified function is called with a reference to the parsed value.
+//!
+//! The function must return `Result<(), E>` where `E` implements [`Display`](core::fmt::Display).
+//! If it returns `Err`, parsing fails and the error message is stored in [`ParseError`].
+//!
+//! If specified for a variant, parsing proceeds to the next variant when validation fails.
+//!
+//! ```rust
+//! use parse_display::FromStr;
+//! #[derive(FromStr, Debug, PartialEq)]
+//! #[display("{start}..{end}")]
+//! #[from_str(validate = Self::validate)]
+//! struct Range {
+//!     start: u32,
+//!     end: u32,
+//! }
+//!
+//! impl Range {
+//!     fn validate(&self) -> Result<(), String> {
+//!         if self.start <= self.end {
+//!             Ok(())
+//!         } else {
+//!             Err(format!("{} > {}", self.start, self.end))
+//!         }
+//!     }
+//! }
+//!
+//! assert_eq!("1..2".parse(), Ok(Range { start: 1, end: 2 }));
+//! assert_eq!("2..1".parse::<Range>().unwrap_err().to_string(), "2 > 1");
+//! ```
+//!
 //! ## `#[from_str(ignore)]`
 //!
 //! Specifying this attribute for a variant will not generate `FromStr` implementation for that variant.
@@ -630,6 +1171,56 @@
 //! assert_eq!("1".parse(), Ok(HasIgnore::B(1)));
 //! ```
 //!
+//! ## `#[from_str(priority = ...)]`
+//!
+//! Variants with higher priority are tried first when parsing enum.
+//! The priority is an integer, and the default is `0`.
+//! Variants with the same priority are tried in the order described in [Variant order](#variant-order).
+//!
+//! ```rust
+//! use parse_display::FromStr;
+//!
+//! #[derive(FromStr, Debug, Eq, PartialEq)]
+//! enum Address {
+//!     #[display("{0}")]
+//!     Host(String),
+//!     #[display("{0}:{1}")]
+//!     #[from_str(priority = 1)]
+//!     HostPort(String, u16),
+//! }
+//!
+//! assert_eq!("a:80".parse(), Ok(Address::HostPort("a".into(), 80)));
+//! assert_eq!("a".parse(), Ok(Address::Host("a".into())));
+//! ```
+//!
+//! ## `#[from_str(match = "...")]`
+//!
+//! Specifies how to choose the variant to be tried first among variants with the same priority.
+//!
+//! | value               | order                                                                                |
+//! | ------------------- | ------------------------------------------------------------------------------------ |
+//! | `"first"` (default) | [Variant order](#variant-order)                                                      |
+//! | `"longest"`         | variants with longer text outside fields first, then [Variant order](#variant-order) |
+//!
+//! With `"longest"`, a variant with a more specific format is tried before a variant with a more generic format regardless of declaration order.
+//! The length of the text outside fields is the number of characters that the format or the regex always matches outside field captures.
+//!
+//! ```rust
+//! use parse_display::FromStr;
+//!
+//! #[derive(FromStr, Debug, Eq, PartialEq)]
+//! #[from_str(match = "longest")]
+//! enum Host {
+//!     #[display("{0}")]
+//!     Other(String),
+//!     #[display("{0}.local")]
+//!     Local(String),
+//! }
+//!
+//! assert_eq!("a.local".parse(), Ok(Host::Local("a".into())));
+//! assert_eq!("a.com".parse(), Ok(Host::Other("a.com".into())));
+//! ```
+//!
 //! ## `#[from_str(default)]`
 //!
 //! If this attribute is specified, the default value is used for fields not included in the input.
@@ -676,6 +1267,50 @@
 //! assert_eq!("10".parse(), Ok(MyStruct { a:0, b:10 }));
 //! ```
 //!
+//! ## `#[from_str(default = ...)]`
+//!
+//! Like `#[from_str(default)]`, but the specified expression is used instead of `Default::default()`.
+//!
+//! In the expression for field, you can use a variable with the same name as the field read from the input.
+//! Like [`#[from_str(new = ...)]`](#from_strnew--), in tuple struct, variables are named with a leading underscore and their index. (e.g. `_0`, `_1`).
+//!
+//! ```rust
+//! use parse_display::FromStr;
+//!
+//! #[derive(FromStr, PartialEq, Debug)]
+//! #[display("{host}")]
+//! struct Server {
+//!   host: String,
+//!   #[from_str(default = 8080)]
+//!   port: u16,
+//!   #[from_str(default = format!("{host}:{port}"))]
+//!   addr: String,
+//! }
+//! assert_eq!("localhost".parse(), Ok(Server {
+//!   host: "localhost".into(),
+//!   port: 8080,
+//!   addr: "localhost:8080".into(),
+//! }));
+//! ```
+//!
+//! Fields read from the input can be used from any expression.
+//! Fields with default value expression can be used only from the expressions of fields declared after them.
+//!
+//! If an attribute is specified for struct, the expression is used instead of the struct's default value.
+//!
+//! ```rust
+//! use parse_display::FromStr;
+//!
+//! #[derive(FromStr, PartialEq, Debug)]
+//! #[display("{b}")]
+//! #[from_str(default = MyStruct { a: 99, b: 99 })]
+//! struct MyStruct {
+//!   a: u32,
+//!   b: u32,
+//! }
+//! assert_eq!("10".parse(), Ok(MyStruct { a:99, b:10 }));
+//! ```
+//!
 //! ## `#[from_str(default_fields(...))]`
 //!
 //! You can use `#[from_str(default_fields(...))]` if you want to set default values for the same-named fields of multiple variants.
@@ -694,6 +1329,75 @@
 //! assert_eq!("VarA-10".parse(), Ok(MyEnum::VarA { a:10, b:0, c:0 }));
 //! assert_eq!("VarB-10".parse(), Ok(MyEnum::VarB { a:10, b:0, c:0 }));
 //! ```
+//!
+//! ## `#[display(dump(...))]`, `#[from_str(dump(...))]`
+//!
+//! Outputs the code generated by the derive macro for debugging.
+//! `#[display(dump(...))]` applies to both `Display` and `FromStr`, and `#[from_str(dump(...))]` applies to `FromStr` only.
+//! For `FromStr`, the regex used for parsing and the fields corresponding to its captures are also output.
+//!
+//! | attribute       | output                                                                              |
+//! | --------------- | ----------------------------------------------------------------------------------- |
+//! | `dump`          | compile error by panic                                                              |
+//! | `dump(warning)` | warning reported by the `deprecated` lint                                           |
+//! | `dump(file)`    | file `parse-display-dump/{type}.{trait}.txt` in `OUT_DIR` (requires a build script) |
+//!
+//! ## Runtime format
+//!
+//! [`Format`] is a format specified at runtime, such as a format read from a configuration file.
+//! It can display and parse types that implement [`FieldsByName`], which can be implemented by `#[derive(FieldsByName)]`.
+//!
+//! In the format, `{name}` is the field named `name`, and `{{` and `}}` are escaped brackets.
+//! Format parameters such as `{name:>10}` are not supported.
+//!
+//! Fields are displayed and parsed in the same way as `#[derive(Display, FromStr)]`, using the field format specified by [`#[display("...")]`](#field-format) and other field attributes.
+//! Missing fields are set by [`#[from_str(default)]`](#from_strdefault) or [`#[from_str(default = ...)]`](#from_strdefault--), and are errors otherwise.
+//!
+//! This feature requires the `std` feature.
+//!
+//! ```rust
+//! use parse_display::{FieldsByName, Format};
+//!
+//! #[derive(FieldsByName, PartialEq, Debug)]
+//! struct MyStruct {
+//!   a: u32,
+//!   b: u32,
+//! }
+//!
+//! let format = Format::<MyStruct>::new("{a}-{b}").unwrap();
+//! assert_eq!(format.display(&MyStruct { a: 10, b: 20 }).to_string(), "10-20");
+//! assert_eq!(format.parse("10-20"), Ok(MyStruct { a: 10, b: 20 }));
+//! assert!(Format::<MyStruct>::new("{c}").is_err());
+//! ```
+//!
+//! ## `parse!`
+//!
+//! [`parse!`] parses a string with a format without declaring a type.
+//!
+//! `parse!("format", input)` with positional arguments such as `{}` and `{0}` returns `Result<(T0, T1, ...), ParseError>`.
+//! Each value is parsed by `FromStr`, and its type is inferred from the usage.
+//!
+//! `parse!("format", input)` with named arguments such as `{name}` assigns the parsed values to the variables with the same names and returns `Result<(), ParseError>`.
+//! The variables are not changed if parsing fails.
+//!
+//! The format is the same as [`#[display("...")]`](#display), except that `{?...}` and `{#...}` are not supported,
+//! and positional and named arguments cannot be mixed.
+//! [Format types](#format-parameter) such as `{:x}` and `{:e}` are supported only for primitive number types.
+//!
+//! This feature requires the `std` feature.
+//!
+//! ```rust
+//! use parse_display::parse;
+//!
+//! let (a, b, c): (u32, String, u16) = parse!("{}-{}:{}", "10-abc:80").unwrap();
+//! assert_eq!((a, b.as_str(), c), (10, "abc", 80));
+//!
+//! let mut host = String::new();
+//! let mut port = 0u16;
+//! parse!("{host}:{port}", "localhost:8080").unwrap();
+//! assert_eq!(host, "localhost");
+//! assert_eq!(port, 8080);
+//! ```
 // #![include_doc("../../README.md", end("## License"))]
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -703,23 +1407,235 @@ use core::fmt::{Display, Formatter, Result};
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "std")]
+mod format;
+mod quoted;
+
+#[cfg(feature = "std")]
+pub use format::{Format, FormatDisplay};
+
+#[doc(hidden)]
+pub use quoted::QuotedDisplay;
+
+/// Implemented by `#[derive(FromStr)]` for enums whose variants are all parsed from fixed text.
+///
+/// `REGEX` matches the text of all variants, and is used for fields of the type.
+#[doc(hidden)]
+pub trait FromStrRegex {
+    const REGEX: &'static str;
+}
+
 #[cfg(feature = "std")]
 pub mod helpers {
+    use core::fmt::Display;
+
+    pub use crate::quoted::unquote;
     pub use once_cell;
     pub use regex;
+
+    pub fn escape_display(value: impl Display) -> String {
+        regex::escape(&value.to_string())
+    }
+
+    /// Caches a regex that contains the values of format arguments.
+    ///
+    /// The regex is built again only when the values change.
+    pub struct RegexCache(std::sync::Mutex<Option<regex::Regex>>);
+    impl RegexCache {
+        #[allow(clippy::new_without_default)]
+        pub const fn new() -> Self {
+            Self(std::sync::Mutex::new(None))
+        }
+        pub fn get(&self, parts: &[&str]) -> regex::Regex {
+            let s = parts.concat();
+            let mut cache = self.0.lock().unwrap_or_else(|e| e.into_inner());
+            match &*cache {
+                Some(re) if re.as_str() == s => re.clone(),
+                _ => {
+                    let re = regex::Regex::new(&s).unwrap();
+                    *cache = Some(re.clone());
+                    re
+                }
+            }
+        }
+    }
+
+    /// Gets the regex of a field type, if the type implements [`FromStrRegex`](crate::FromStrRegex).
+    ///
+    /// For other types, [`TypeRegexDefault::get`] is called instead.
+    pub struct TypeRegex<T: ?Sized>(core::marker::PhantomData<T>);
+    impl<T: ?Sized> TypeRegex<T> {
+        #[allow(clippy::new_without_default)]
+        pub const fn new() -> Self {
+            Self(core::marker::PhantomData)
+        }
+    }
+    impl<T: ?Sized + crate::FromStrRegex> TypeRegex<T> {
+        pub fn get(&self) -> Option<&'static str> {
+            Some(T::REGEX)
+        }
+    }
+    pub trait TypeRegexDefault {
+        fn get(&self) -> Option<&'static str> {
+            None
+        }
+    }
+    impl<T: ?Sized> TypeRegexDefault for TypeRegex<T> {}
+
+    pub fn type_regex(regex: Option<&str>, trim: bool, default: &str) -> String {
+        match regex {
+            Some(regex) if trim => format!(r"\s*(?:{regex})\s*"),
+            Some(regex) => regex.into(),
+            None => default.into(),
+        }
+    }
+
+    /// Finds text in `s` that is not covered by the segments matched by `#[from_str(any_order)]`.
+    ///
+    /// `spans` are the ranges of the last match of each segment.
+    /// Since all segments are matched at the end of the text, some text is not covered if a segment is matched more than once.
+    /// Returns the first text not covered, without the leading separator.
+    pub fn any_order_gap<'a>(
+        s: &'a str,
+        sep: &str,
+        spans: &mut [Option<(usize, usize)>],
+    ) -> Option<&'a str> {
+        spans.sort_unstable();
+        let mut last = None;
+        for &(start, end) in spans.iter().flatten() {
+            let (gap, expected) = match last {
+                Some(last) => (&s[last..start], sep),
+                None => (&s[..start], ""),
+            };
+            if gap != expected {
+                return Some(gap.strip_prefix(expected).unwrap_or(gap));
+            }
+            last = Some(end);
+        }
+        let gap = &s[last.unwrap_or(0)..];
+        if gap.is_empty() {
+            None
+        } else {
+            Some(gap.strip_prefix(sep).unwrap_or(gap))
+        }
+    }
+
+    pub trait FromStrRadix: Sized {
+        fn from_str_radix(s: &str, radix: u32, prefix: &str) -> Result<Self, crate::ParseError>;
+    }
+
+    fn strip_radix_prefix<'a>(s: &'a str, prefix: &str) -> Result<&'a str, crate::ParseError> {
+        let s = s.strip_prefix('+').unwrap_or(s);
+        match s.strip_prefix(prefix) {
+            Some(s) if !s.starts_with('+') => Ok(s),
+            _ => Err(crate::ParseError::new()),
+        }
+    }
+
+    pub trait FromStrExp: Sized {
+        fn from_str_exp(s: &str) -> Result<Self, crate::ParseError>;
+    }
+
+    macro_rules! impl_from_str_int {
+        ($($ty:ty : $unsigned:ty),*) => {
+            $(
+                impl FromStrRadix for $ty {
+                    fn from_str_radix(s: &str, radix: u32, prefix: &str) -> Result<Self, crate::ParseError> {
+                        let s = strip_radix_prefix(s, prefix)?;
+                        // Signed integers are formatted as two's complement.
+                        match <$unsigned>::from_str_radix(s, radix) {
+                            Ok(value) => Ok(value as $ty),
+                            Err(_) => Err(crate::ParseError::new()),
+                        }
+                    }
+                }
+                impl FromStrExp for $ty {
+                    fn from_str_exp(s: &str) -> Result<Self, crate::ParseError> {
+                        let (mantissa, exp) = s
+                            .split_once(|c| c == 'e' || c == 'E')
+                            .ok_or_else(crate::ParseError::new)?;
+                        let exp: usize = exp.parse().map_err(|_| crate::ParseError::new())?;
+                        // Larger exponents overflow all integer types (`u128::MAX` has 39 digits),
+                        // and are rejected before building a string of that length.
+                        if exp > 39 {
+                            return Err(crate::ParseError::new());
+                        }
+                        let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+                        if frac.len() > exp {
+                            return Err(crate::ParseError::new());
+                        }
+                        let s = format!("{int}{frac}{}", "0".repeat(exp - frac.len()));
+                        s.parse().map_err(|_| crate::ParseError::new())
+                    }
+                }
+            )*
+        };
+    }
+    impl_from_str_int!(
+        i8: u8,
+        i16: u16,
+        i32: u32,
+        i64: u64,
+        i128: u128,
+        isize: usize,
+        u8: u8,
+        u16: u16,
+        u32: u32,
+        u64: u64,
+        u128: u128,
+        usize: usize
+    );
+
+    impl FromStrExp for f32 {
+        fn from_str_exp(s: &str) -> Result<Self, crate::ParseError> {
+            s.parse().map_err(|_| crate::ParseError::new())
+        }
+    }
+    impl FromStrExp for f64 {
+        fn from_str_exp(s: &str) -> Result<Self, crate::ParseError> {
+            s.parse().map_err(|_| crate::ParseError::new())
+        }
+    }
 }
 
-pub use parse_display_derive::{Display, FromStr};
+pub use parse_display_derive::{Display, FieldsByName, FromStr};
+
+/// Parses a string with a format without declaring a type.
+///
+/// See [`parse!`](crate#parse) for details.
+#[cfg(feature = "std")]
+pub use parse_display_derive::parse;
+
+#[cfg(feature = "std")]
+type Message = std::borrow::Cow<'static, str>;
+#[cfg(not(feature = "std"))]
+type Message = &'static str;
 
 #[derive(Debug, Eq, PartialEq)]
-pub struct ParseError(&'static str);
+pub struct ParseError(Message);
 impl ParseError {
+    #[allow(clippy::useless_conversion)]
     pub fn with_message(message: &'static str) -> Self {
-        Self(message)
+        Self(message.into())
     }
     pub fn new() -> Self {
         Self::with_message("parse failed.")
     }
+
+    /// Create `ParseError` with the message of `e`.
+    ///
+    /// Without `std` feature, the message of `e` is not kept.
+    pub fn from_error(e: impl Display) -> Self {
+        #[cfg(feature = "std")]
+        {
+            Self(e.to_string().into())
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            let _ = e;
+            Self::new()
+        }
+    }
 }
 impl Default for ParseError {
     fn default() -> Self {
@@ -735,10 +1651,30 @@ impl Display for ParseError {
 #[cfg(feature = "std")]
 impl std::error::Error for ParseError {
     fn description(&self) -> &str {
-        self.0
+        &self.0
     }
 }
 
+/// Access to fields by name, used by [`Format`].
+///
+/// This trait can be implemented by `#[derive(FieldsByName)]`.
+pub trait FieldsByName: Sized {
+    /// The names of the fields.
+    const FIELD_NAMES: &'static [&'static str];
+
+    /// Format the field named `name`.
+    ///
+    /// Returns `None` if there is no field named `name`.
+    fn fmt_field(&self, name: &str, f: &mut Formatter) -> Option<Result>;
+
+    /// Create a value from the string values of the fields.
+    ///
+    /// `fields` returns the string value of the field with the specified name, or `None` if the field is missing.
+    fn from_fields<'a>(
+        fields: &dyn Fn(&str) -> Option<&'a str>,
+    ) -> core::result::Result<Self, ParseError>;
+}
+
 pub trait IntoResult<T> {
     type Err;
     fn into_result(self) -> core::result::Result<T, Self::Err>;
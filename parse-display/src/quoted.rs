@@ -0,0 +1,57 @@
+use core::fmt::{Display, Formatter, Result, Write};
+
+#[doc(hidden)]
+pub struct QuotedDisplay<T> {
+    value: T,
+    quote: char,
+}
+impl<T: Display> QuotedDisplay<T> {
+    pub fn new(value: T, quote: char) -> Self {
+        Self { value, quote }
+    }
+}
+impl<T: Display> Display for QuotedDisplay<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        f.write_char(self.quote)?;
+        write!(
+            EscapeWriter {
+                f,
+                quote: self.quote
+            },
+            "{}",
+            self.value
+        )?;
+        f.write_char(self.quote)
+    }
+}
+
+struct EscapeWriter<'a, 'b> {
+    f: &'a mut Formatter<'b>,
+    quote: char,
+}
+impl Write for EscapeWriter<'_, '_> {
+    fn write_str(&mut self, s: &str) -> Result {
+        for c in s.chars() {
+            if c == self.quote || c == '\\' {
+                self.f.write_char('\\')?;
+            }
+            self.f.write_char(c)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn unquote(s: &str, quote: char) -> Option<String> {
+    let s = s.strip_prefix(quote)?.strip_suffix(quote)?;
+    let mut value = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => value.push(chars.next()?),
+            c if c == quote => return None,
+            c => value.push(c),
+        }
+    }
+    Some(value)
+}
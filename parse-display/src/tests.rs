@@ -1,4 +1,5 @@
 #[test]
+#[allow(dead_code)]
 fn test_crate() {
     #[derive(crate::Display)]
     #[display(crate = crate)]
@@ -14,6 +15,7 @@ mod my_mod {
 }
 
 #[test]
+#[allow(dead_code)]
 fn test_crate_mod() {
     #[derive(crate::Display)]
     #[display(crate = my_mod::my_crate)]